@@ -0,0 +1,60 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Benchmarks for [`WindowEventReducer::reduce`].
+//!
+//! Run with `cargo bench -p ui-events-winit --features test-util --bench reduce`.
+
+// Criterion's macros generate undocumented functions.
+#![allow(missing_docs)]
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use ui_events_winit::{test_util::*, WindowEventReducer};
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
+
+/// A representative mix of mouse, wheel, and touch input.
+fn input_mix() -> Vec<WindowEvent> {
+    let mut events = vec![cursor_entered()];
+    for i in 0..100 {
+        let x = f64::from(i);
+        events.push(cursor_moved(x, x * 0.5));
+        match i % 10 {
+            0 => events.push(mouse_input(ElementState::Pressed, MouseButton::Left)),
+            1 => events.push(mouse_input(ElementState::Released, MouseButton::Left)),
+            5 => events.push(mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0))),
+            6 => events.push(mouse_wheel(MouseScrollDelta::PixelDelta(
+                PhysicalPosition::new(0.0, 12.5),
+            ))),
+            _ => {}
+        }
+    }
+    for id in 0..4 {
+        events.push(touch(TouchPhase::Started, id, 10.0, 10.0, None));
+        for i in 0..20 {
+            let d = f64::from(i);
+            events.push(touch(TouchPhase::Moved, id, 10.0 + d, 10.0 + d, None));
+        }
+        events.push(touch(TouchPhase::Ended, id, 30.0, 30.0, None));
+    }
+    events.push(cursor_left());
+    events
+}
+
+fn reduce(c: &mut Criterion) {
+    let events = input_mix();
+    let mut group = c.benchmark_group("reduce");
+    group.throughput(Throughput::Elements(events.len() as u64));
+    group.bench_function("input_mix", |b| {
+        let mut reducer = WindowEventReducer::default();
+        b.iter(|| {
+            for event in &events {
+                black_box(reducer.reduce(black_box(event)));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, reduce);
+criterion_main!(benches);
@@ -0,0 +1,175 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Sources of the time of events.
+//!
+//! A [`WindowEventReducer`] timestamps the events it processes with a [`Clock`],
+//! which is a `StdClock` unless one is set with [`WindowEventReducer::set_clock`].
+//! `StdClock` needs the `std` feature. Without it, a reducer is created with
+//! [`WindowEventReducer::with_clock`], and another clock such as a [`ManualClock`].
+//!
+//! [`WindowEventReducer`]: crate::WindowEventReducer
+//! [`WindowEventReducer::set_clock`]: crate::WindowEventReducer::set_clock
+//! [`WindowEventReducer::with_clock`]: crate::WindowEventReducer::with_clock
+
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// A monotonic source of time.
+pub trait Clock {
+    /// The current time in nanoseconds.
+    ///
+    /// This must never decrease, and should be positive, as `0` is the
+    /// default [`time`](ui_events::pointer::PointerState::time) of a state.
+    fn now_nanos(&self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u64,
+{
+    fn now_nanos(&self) -> u64 {
+        self()
+    }
+}
+
+/// A [`Clock`] measuring the time since it was created, with [`Instant`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct StdClock {
+    /// Time of creation.
+    epoch: Instant,
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    /// Nanoseconds since the clock was created.
+    ///
+    /// This is always positive, so that the first event is distinguishable
+    /// from states with the default time of `0`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn now_nanos(&self) -> u64 {
+        (self.epoch.elapsed().as_nanos() as u64).max(1)
+    }
+}
+
+/// A [`Clock`] which only moves when told to, for tests and replaying recorded input.
+///
+/// Clones share the same time, so a clone can be given to a reducer, and the
+/// original kept to advance it.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    /// The current time in nanoseconds.
+    now: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// A clock at `nanos`.
+    pub fn new(nanos: u64) -> Self {
+        Self {
+            now: Arc::new(AtomicU64::new(nanos)),
+        }
+    }
+
+    /// Set the time to `nanos`.
+    pub fn set(&self, nanos: u64) {
+        self.now.store(nanos, Ordering::Relaxed);
+    }
+
+    /// Move the time forward by `duration`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn advance(&self, duration: Duration) {
+        self.now
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_nanos(&self) -> u64 {
+        self.now.load(Ordering::Relaxed)
+    }
+}
+
+/// The clock of a [`WindowEventReducer`](crate::WindowEventReducer).
+///
+/// This is boxed rather than a type parameter of the reducer, so that
+/// [`set_clock`](crate::WindowEventReducer::set_clock) can replace it with a clock
+/// of another type, and the type of the reducer doesn't depend on the `std` feature.
+pub(crate) struct ReducerClock(Box<dyn Clock + Send>);
+
+impl ReducerClock {
+    /// A reducer clock reading `clock`.
+    pub(crate) fn new(clock: impl Clock + Send + 'static) -> Self {
+        Self(Box::new(clock))
+    }
+
+    /// The current time in nanoseconds.
+    pub(crate) fn now_nanos(&self) -> u64 {
+        self.0.now_nanos()
+    }
+}
+
+impl fmt::Debug for ReducerClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReducerClock").finish_non_exhaustive()
+    }
+}
+
+// These tests don't need `std`, unlike those of the reducer which use its default clock.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use crate::{WindowEventReducer, WindowEventTranslation};
+    use ui_events::pointer::{PointerEvent, PointerId};
+    use winit::event::{ElementState, MouseButton};
+
+    /// The time of events and of holds is read from the clock.
+    #[test]
+    fn manual_clock() {
+        let clock = ManualClock::new(1_000_000_000);
+        let mut reducer = WindowEventReducer::with_clock(clock.clone());
+        let press = mouse_input(ElementState::Pressed, MouseButton::Left);
+        let release = mouse_input(ElementState::Released, MouseButton::Left);
+        let click = |reducer: &mut WindowEventReducer| {
+            let down = match reducer.reduce(&press) {
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) => down,
+                other => panic!("expected a down, got {other:?}"),
+            };
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(
+                reducer.time_since_down(PointerId::PRIMARY),
+                Some(Duration::from_millis(100)),
+                "held for the time the clock advanced"
+            );
+            reducer.reduce(&release);
+            down.state
+        };
+
+        assert_eq!(click(&mut reducer).time, 1_000_000_000, "the clock's time");
+        clock.advance(Duration::from_millis(300));
+        assert_eq!(click(&mut reducer).count, 2, "a double click");
+        clock.advance(Duration::from_millis(600));
+        assert_eq!(
+            click(&mut reducer).count,
+            1,
+            "an expired sequence is not continued"
+        );
+    }
+}
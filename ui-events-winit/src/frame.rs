@@ -0,0 +1,141 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Per frame summaries of input, for immediate mode consumers.
+
+use alloc::vec::Vec;
+
+use ui_events::{
+    keyboard::{KeyboardEvent, Modifiers},
+    pointer::{PointerButtonEvent, PointerButtons, PointerEvent, PointerType},
+    ScrollDelta,
+};
+use winit::dpi::PhysicalPosition;
+
+use crate::WindowEventTranslation;
+
+/// A summary of the input of a frame, from [`WindowEventReducer::reduce_frame`].
+///
+/// This is for immediate mode renderers, which poll the input state each frame
+/// instead of handling every event. It only summarizes the mouse, so use
+/// [`WindowEventReducer::reduce_into`] or [`WindowEventReducer::reduce_all`] for touch
+/// input, which can produce several translations per event.
+///
+/// [`WindowEventReducer::reduce_frame`]: crate::WindowEventReducer::reduce_frame
+/// [`WindowEventReducer::reduce_into`]: crate::WindowEventReducer::reduce_into
+/// [`WindowEventReducer::reduce_all`]: crate::WindowEventReducer::reduce_all
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameInput {
+    /// Position of the cursor at the end of the frame, if it is in the window.
+    pub position: Option<PhysicalPosition<f64>>,
+    /// Buttons held at the end of the frame.
+    pub buttons: PointerButtons,
+    /// Buttons pressed during the frame, including those released again.
+    pub pressed: PointerButtons,
+    /// Buttons released during the frame, including those pressed again.
+    pub released: PointerButtons,
+    /// Modifiers held at the end of the frame.
+    pub modifiers: Modifiers,
+    /// Net scroll in lines during the frame, horizontal then vertical.
+    pub line_scroll: (f32, f32),
+    /// Net scroll in physical pixels during the frame.
+    pub pixel_scroll: PhysicalPosition<f64>,
+    /// Keyboard events of the frame, in order.
+    pub keys: Vec<KeyboardEvent>,
+}
+
+impl FrameInput {
+    /// Add a translation to the summary.
+    pub(crate) fn record(&mut self, translation: WindowEventTranslation) {
+        let e = match translation {
+            WindowEventTranslation::Keyboard(e) => {
+                self.keys.push(e);
+                return;
+            }
+            WindowEventTranslation::Pointer(e) => e,
+            _ => return,
+        };
+        match e {
+            PointerEvent::Down(PointerButtonEvent {
+                pointer,
+                button: Some(button),
+                ..
+            }) if pointer.pointer_type == PointerType::Mouse => self.pressed.insert(button),
+            PointerEvent::Up(PointerButtonEvent {
+                pointer,
+                button: Some(button),
+                ..
+            }) if pointer.pointer_type == PointerType::Mouse => self.released.insert(button),
+            PointerEvent::Scroll(e) if e.pointer.pointer_type == PointerType::Mouse => {
+                match e.delta {
+                    ScrollDelta::LineDelta(x, y) => {
+                        self.line_scroll.0 += x;
+                        self.line_scroll.1 += y;
+                    }
+                    ScrollDelta::PixelDelta(p) => {
+                        self.pixel_scroll.x += p.x;
+                        self.pixel_scroll.y += p.y;
+                    }
+                    ScrollDelta::PageDelta(..) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// The tests use default reducers, whose clock needs `std`.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::test_util::*;
+    use crate::WindowEventReducer;
+    use ui_events::pointer::{PointerButton, PointerButtons};
+    use winit::dpi::PhysicalPosition;
+    use winit::event::{ElementState, MouseButton, MouseScrollDelta};
+
+    /// The events of a frame are summarized.
+    #[test]
+    fn reduce_frame() {
+        let mut reducer = WindowEventReducer::default();
+        let frame = reducer.reduce_frame(&[
+            cursor_entered(),
+            cursor_moved(1.0, 2.0),
+            mouse_input(ElementState::Pressed, MouseButton::Left),
+            mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0)),
+            mouse_wheel(MouseScrollDelta::LineDelta(0.5, 2.0)),
+            mouse_input(ElementState::Released, MouseButton::Left),
+            mouse_input(ElementState::Pressed, MouseButton::Right),
+            cursor_moved(5.0, 6.0),
+        ]);
+        assert_eq!(
+            frame.position,
+            Some(PhysicalPosition::new(5.0, 6.0)),
+            "the final position"
+        );
+        assert_eq!(
+            frame.buttons,
+            PointerButtons::from(PointerButton::Secondary),
+            "the held buttons"
+        );
+        assert_eq!(
+            frame.pressed,
+            PointerButton::Primary | PointerButton::Secondary,
+            "the pressed buttons"
+        );
+        assert_eq!(
+            frame.released,
+            PointerButtons::from(PointerButton::Primary),
+            "the released buttons"
+        );
+        assert_eq!(frame.line_scroll, (0.5, 3.0), "the net scroll");
+        assert!(frame.keys.is_empty(), "no keys");
+
+        let frame = reducer.reduce_frame(&[]);
+        assert!(frame.pressed.is_empty(), "transitions are per frame");
+        assert_eq!(
+            frame.buttons,
+            PointerButtons::from(PointerButton::Secondary),
+            "the state is kept"
+        );
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Gesture recognition, enabled by the `gestures` feature.
+
+use alloc::vec::Vec;
+
+use ui_events::{
+    gesture::{GestureEvent, GestureId, SwipeDirection},
+    pointer::{
+        PointerButtonEvent, PointerEvent, PointerId, PointerInfo, PointerType, PointerUpdate,
+    },
+};
+use winit::dpi::PhysicalPosition;
+
+/// Configuration of three finger gesture recognition.
+///
+/// A gesture starts when three touches go down together, and is recognized
+/// when the last of them lifts. It is a tap when no touch moved far,
+/// and a swipe when all touches moved far in the same direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThreeFingerGestures {
+    /// Maximum time between the first and the third touch going down, in nanoseconds.
+    pub sync_window: u64,
+    /// Maximum distance a touch may move during a tap, in physical pixels.
+    pub tap_slop: f64,
+    /// Maximum duration of a tap, in nanoseconds.
+    pub max_tap_duration: u64,
+    /// Minimum average distance the touches must move for a swipe, in physical pixels.
+    pub min_swipe_distance: f64,
+    /// Which events are delivered for the touches of a gesture.
+    pub streams: GestureStreams,
+}
+
+impl Default for ThreeFingerGestures {
+    fn default() -> Self {
+        Self {
+            sync_window: 100_000_000,
+            tap_slop: 10.0,
+            max_tap_duration: 300_000_000,
+            min_swipe_distance: 50.0,
+            streams: GestureStreams::Both,
+        }
+    }
+}
+
+/// Which events are delivered for the touches of a three finger gesture.
+///
+/// Use [`WindowEventReducer::reduce_into_tagged`] to correlate the pointer events
+/// with the gestures they produce.
+///
+/// [`WindowEventReducer::reduce_into_tagged`]: crate::WindowEventReducer::reduce_into_tagged
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GestureStreams {
+    /// Deliver both the pointer events of the touches, and the recognized gesture
+    /// after the [`PointerEvent::Up`] of the last of them.
+    #[default]
+    Both,
+    /// Deliver the recognized gesture instead of the pointer events of its touches.
+    ///
+    /// When the third touch goes down in time, the touches still down are
+    /// [cancelled](PointerEvent::Cancel), and no more pointer events are delivered
+    /// for the three touches until they lift, even if they turn out to be neither
+    /// a tap nor a swipe. Touches which can't take part in a gesture, such as a
+    /// fourth one, are delivered as usual.
+    GesturesOnly,
+}
+
+/// A touch taking part in a three finger gesture.
+#[derive(Clone, Debug)]
+struct Finger {
+    /// Pointer ID of the touch, or `None` once it lifted.
+    pointer_id: Option<PointerId>,
+    /// Pointer info of the touch, to cancel it.
+    pointer: PointerInfo,
+    /// Whether the pointer events of the touch are replaced by the gesture.
+    claimed: bool,
+    /// Position where the touch went down.
+    start: PhysicalPosition<f64>,
+    /// Last known position of the touch.
+    position: PhysicalPosition<f64>,
+}
+
+impl Finger {
+    /// Displacement since the touch went down.
+    fn displacement(&self) -> (f64, f64) {
+        (
+            self.position.x - self.start.x,
+            self.position.y - self.start.y,
+        )
+    }
+}
+
+/// Recognizer for [`ThreeFingerGestures`].
+#[derive(Clone, Debug)]
+pub(crate) struct ThreeFingerRecognizer {
+    /// Configuration of recognition.
+    pub(crate) config: ThreeFingerGestures,
+    /// Touches of the current gesture, including lifted ones.
+    fingers: Vec<Finger>,
+    /// Number of touches of the current gesture still down.
+    active: usize,
+    /// Time the first touch of the current gesture went down.
+    start_time: u64,
+    /// Whether the current gesture can still be recognized.
+    valid: bool,
+    /// Id of the current gesture.
+    id: Option<GestureId>,
+    /// Value of the next gesture id.
+    next_id: u64,
+}
+
+/// What a [`ThreeFingerRecognizer`] made of a pointer event.
+#[derive(Debug, Default)]
+pub(crate) struct Observation {
+    /// Id of the candidate gesture the event belongs to.
+    pub(crate) id: Option<GestureId>,
+    /// Whether the event is replaced by the gesture, and must not be delivered.
+    pub(crate) claimed: bool,
+    /// Touches to cancel before the event, because the gesture claimed them.
+    pub(crate) cancelled: Vec<PointerInfo>,
+    /// The gesture recognized on this event, delivered after it.
+    pub(crate) gesture: Option<GestureEvent>,
+}
+
+impl ThreeFingerRecognizer {
+    /// A recognizer with `config`, waiting for the first gesture.
+    pub(crate) fn new(config: ThreeFingerGestures) -> Self {
+        Self {
+            config,
+            fingers: Vec::new(),
+            active: 0,
+            start_time: 0,
+            valid: false,
+            id: None,
+            next_id: 1,
+        }
+    }
+
+    /// Observe a [`PointerEvent`] before it is delivered.
+    pub(crate) fn observe(&mut self, e: &PointerEvent) -> Observation {
+        let mut observation = Observation::default();
+        match e {
+            PointerEvent::Down(PointerButtonEvent { pointer, state, .. })
+                if pointer.pointer_type == PointerType::Touch =>
+            {
+                if self.active == 0 {
+                    self.fingers.clear();
+                    self.start_time = state.time;
+                    self.valid = true;
+                    self.id = GestureId::new(self.next_id);
+                    self.next_id += 1;
+                }
+                if self.fingers.len() == 3
+                    || state.time.saturating_sub(self.start_time) > self.config.sync_window
+                {
+                    self.valid = false;
+                }
+                self.active += 1;
+                self.fingers.push(Finger {
+                    pointer_id: pointer.pointer_id,
+                    pointer: *pointer,
+                    claimed: false,
+                    start: state.position,
+                    position: state.position,
+                });
+                if self.valid
+                    && self.fingers.len() == 3
+                    && self.config.streams == GestureStreams::GesturesOnly
+                {
+                    // The third touch makes a candidate, which takes over its touches.
+                    for finger in &mut self.fingers {
+                        finger.claimed = true;
+                        if finger.pointer_id.is_some() && finger.pointer_id != pointer.pointer_id {
+                            observation.cancelled.push(finger.pointer);
+                        }
+                    }
+                }
+                observation.claimed = self.fingers.last().is_some_and(|f| f.claimed);
+            }
+            PointerEvent::Move(PointerUpdate {
+                pointer, current, ..
+            }) => {
+                let Some(finger) = self.finger(pointer) else {
+                    return observation;
+                };
+                finger.position = current.position;
+                observation.claimed = finger.claimed;
+            }
+            PointerEvent::Cancel(pointer) => {
+                let Some(finger) = self.finger(pointer) else {
+                    return observation;
+                };
+                finger.pointer_id = None;
+                observation.claimed = finger.claimed;
+                self.valid = false;
+                self.active -= 1;
+            }
+            PointerEvent::Up(PointerButtonEvent { pointer, state, .. }) => {
+                let Some(finger) = self.finger(pointer) else {
+                    return observation;
+                };
+                finger.position = state.position;
+                finger.pointer_id = None;
+                observation.claimed = finger.claimed;
+                self.active -= 1;
+                if self.active == 0 && self.valid && self.fingers.len() == 3 {
+                    self.valid = false;
+                    observation.gesture =
+                        self.recognize(state.time.saturating_sub(self.start_time));
+                }
+            }
+            _ => return observation,
+        }
+        observation.id = self.id;
+        observation
+    }
+
+    /// The active finger of `pointer`, if it takes part in the current gesture.
+    fn finger(&mut self, pointer: &PointerInfo) -> Option<&mut Finger> {
+        if pointer.pointer_id.is_none() || pointer.pointer_type != PointerType::Touch {
+            return None;
+        }
+        self.fingers
+            .iter_mut()
+            .find(|finger| finger.pointer_id == pointer.pointer_id)
+    }
+
+    /// Recognize the completed gesture, which lasted `duration` nanoseconds.
+    fn recognize(&self, duration: u64) -> Option<GestureEvent> {
+        let config = &self.config;
+        let id = self.id?;
+        let displacements = self.fingers.iter().map(Finger::displacement);
+        if duration <= config.max_tap_duration
+            && displacements
+                .clone()
+                .all(|(dx, dy)| dx.hypot(dy) <= config.tap_slop)
+        {
+            return Some(GestureEvent::ThreeFingerTap { id });
+        }
+
+        let direction = |(dx, dy): (f64, f64)| match (dx.abs() >= dy.abs(), dx < 0.0, dy < 0.0) {
+            (true, true, _) => SwipeDirection::Left,
+            (true, false, _) => SwipeDirection::Right,
+            (false, _, true) => SwipeDirection::Up,
+            (false, _, false) => SwipeDirection::Down,
+        };
+        let (sum_x, sum_y) = displacements
+            .clone()
+            .fold((0.0, 0.0), |(sx, sy), (dx, dy)| (sx + dx, sy + dy));
+        let mean = (sum_x / 3.0, sum_y / 3.0);
+        let swipe = direction(mean);
+        (mean.0.hypot(mean.1) >= config.min_swipe_distance
+            && displacements.clone().all(|d| direction(d) == swipe))
+        .then_some(GestureEvent::ThreeFingerSwipe {
+            id,
+            direction: swipe,
+        })
+    }
+}
+
+// The tests use default reducers, whose clock needs `std`.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use crate::{WindowEventReducer, WindowEventTranslation};
+    use ui_events::Tagged;
+    use winit::event::TouchPhase;
+
+    /// Three synchronized touches are recognized as a tap or a swipe.
+    #[test]
+    fn three_finger_gestures() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_three_finger_gestures(Some(ThreeFingerGestures::default()));
+        const FINGERS: [(u64, f64); 3] = [(0, 0.0), (1, 100.0), (2, 200.0)];
+
+        let mut gesture = |dx: f64, dy: f64| {
+            let mut out = Vec::new();
+            for (id, x) in FINGERS {
+                reducer.reduce_into(&touch(TouchPhase::Started, id, x, 100.0, None), &mut out);
+            }
+            for step in 1..=4 {
+                let step = f64::from(step) / 4.0;
+                for (id, x) in FINGERS {
+                    let x = x + dx * step;
+                    let we = touch(TouchPhase::Moved, id, x, 100.0 + dy * step, None);
+                    reducer.reduce_into(&we, &mut out);
+                }
+            }
+            assert_eq!(reducer.active_touch_count(), 3, "three touches are active");
+            for (id, x) in FINGERS {
+                let x = x + dx;
+                let we = touch(TouchPhase::Ended, id, x, 100.0 + dy, None);
+                reducer.reduce_into(&we, &mut out);
+            }
+            out.into_iter()
+                .filter_map(|translation| GestureEvent::try_from(translation).ok())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            matches!(
+                gesture(2.0, -3.0)[..],
+                [GestureEvent::ThreeFingerTap { .. }]
+            ),
+            "a tap"
+        );
+        assert!(
+            matches!(
+                gesture(-80.0, 10.0)[..],
+                [GestureEvent::ThreeFingerSwipe {
+                    direction: SwipeDirection::Left,
+                    ..
+                }]
+            ),
+            "a swipe to the left"
+        );
+        assert!(
+            matches!(
+                gesture(5.0, 120.0)[..],
+                [GestureEvent::ThreeFingerSwipe {
+                    direction: SwipeDirection::Down,
+                    ..
+                }]
+            ),
+            "a swipe down"
+        );
+        assert!(gesture(20.0, 20.0).is_empty(), "neither a tap nor a swipe");
+
+        // Two touches aren't a three finger gesture.
+        let mut out = Vec::new();
+        for id in 0..2 {
+            reducer.reduce_into(&touch(TouchPhase::Started, id, 1.0, 1.0, None), &mut out);
+        }
+        for id in 0..2 {
+            reducer.reduce_into(&touch(TouchPhase::Ended, id, 1.0, 1.0, None), &mut out);
+        }
+        assert!(
+            !out.iter()
+                .any(|translation| matches!(translation, WindowEventTranslation::Gesture(..))),
+            "two touches are not recognized"
+        );
+    }
+
+    /// Pointer events of a gesture are tagged with its id.
+    #[test]
+    fn gesture_correlation() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_three_finger_gestures(Some(ThreeFingerGestures::default()));
+
+        let mut out = Vec::new();
+        for phase in [TouchPhase::Started, TouchPhase::Ended] {
+            for (id, x) in [(0, 0.0), (1, 100.0), (2, 200.0)] {
+                reducer.reduce_into_tagged(&touch(phase, id, x, 1.0, None), &mut out);
+            }
+        }
+        let gesture_id = out[0]
+            .context
+            .expect("touches of a candidate gesture are tagged");
+        assert_eq!(out.len(), 7, "six pointer events and the gesture");
+        assert!(
+            out.iter().all(|tagged| tagged.context == Some(gesture_id)),
+            "all translations have the same id"
+        );
+        assert!(
+            matches!(
+                &out[6].event,
+                WindowEventTranslation::Gesture(GestureEvent::ThreeFingerTap { id })
+                    if *id == gesture_id
+            ),
+            "the gesture follows its pointer events, with the same id"
+        );
+
+        out.clear();
+        reducer.reduce_into_tagged(&cursor_moved(1.0, 2.0), &mut out);
+        reducer.reduce_into_tagged(&touch(TouchPhase::Started, 0, 1.0, 1.0, None), &mut out);
+        assert!(
+            matches!(
+                out.as_slice(),
+                [
+                    Tagged { context: None, .. },
+                    Tagged { context: Some(id), .. },
+                ] if *id != gesture_id
+            ),
+            "other events are not tagged, and a new candidate has a new id, got {out:?}"
+        );
+    }
+
+    /// With [`GestureStreams::GesturesOnly`], a gesture replaces the pointer events of its touches.
+    #[test]
+    fn gestures_only() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_three_finger_gestures(Some(ThreeFingerGestures {
+            streams: GestureStreams::GesturesOnly,
+            ..ThreeFingerGestures::default()
+        }));
+
+        let mut out = Vec::new();
+        for phase in [TouchPhase::Started, TouchPhase::Ended] {
+            for (id, x) in [(0, 0.0), (1, 100.0), (2, 200.0)] {
+                reducer.reduce_into_tagged(&touch(phase, id, x, 1.0, None), &mut out);
+            }
+        }
+        // The next touch is delivered, until it makes another candidate.
+        reducer.reduce_into_tagged(&touch(TouchPhase::Started, 3, 9.0, 9.0, None), &mut out);
+        let kinds: Vec<&str> = out
+            .iter()
+            .map(|tagged| match &tagged.event {
+                WindowEventTranslation::Pointer(PointerEvent::Down(..)) => "down",
+                WindowEventTranslation::Pointer(PointerEvent::Cancel(..)) => "cancel",
+                WindowEventTranslation::Gesture(GestureEvent::ThreeFingerTap { .. }) => "tap",
+                other => panic!("unexpected translation {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            ["down", "down", "cancel", "cancel", "tap", "down"],
+            "the first touches are cancelled by the third, and the tap replaces the rest"
+        );
+        let gesture_id = out[0].context;
+        assert!(
+            gesture_id.is_some() && out[..5].iter().all(|tagged| tagged.context == gesture_id),
+            "the cancels and the tap are tagged with the gesture"
+        );
+        assert_ne!(
+            out[5].context, gesture_id,
+            "the next touch is a new candidate"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 //! Support routines for converting keyboard data from [`winit`]
 
 use ui_events::keyboard::{Code, Key, KeyState, KeyboardEvent, Location, Modifiers, NamedKey};
-use winit::keyboard::{KeyLocation, ModifiersState};
+use winit::keyboard::{KeyLocation, ModifiersState, NativeKeyCode};
 
 extern crate alloc;
 use alloc::string::String;
@@ -14,17 +14,30 @@ pub fn from_winit_keyboard_event(
     winit_event: winit::event::KeyEvent,
     mods: ModifiersState,
 ) -> KeyboardEvent {
+    reduce_key_event(&winit_event, mods)
+}
+
+/// Translate a [`winit::event::KeyEvent`] to a [`KeyboardEvent`] without a reducer.
+///
+/// This is stateless, so it is useful when the [`ModifiersState`] is already tracked elsewhere.
+/// Otherwise, [`WindowEventReducer`](crate::WindowEventReducer) keeps track of it for you.
+pub fn reduce_key_event(event: &winit::event::KeyEvent, mods: ModifiersState) -> KeyboardEvent {
     KeyboardEvent {
-        key: from_winit_key(winit_event.logical_key),
-        code: from_winit_code(winit_event.physical_key),
+        key: from_winit_key(event.logical_key.clone()),
+        code: from_winit_code(event.physical_key),
         modifiers: from_winit_modifier_state(mods),
-        location: from_winit_location(winit_event.location),
+        location: from_winit_location(event.location),
         is_composing: false,
-        repeat: winit_event.repeat,
-        state: match winit_event.state {
-            winit::event::ElementState::Pressed => KeyState::Down,
-            winit::event::ElementState::Released => KeyState::Up,
-        },
+        repeat: event.repeat,
+        state: from_winit_element_state(event.state),
+    }
+}
+
+/// Convert a [`winit::event::ElementState`] to a [`ui_events::keyboard::KeyState`].
+pub fn from_winit_element_state(state: winit::event::ElementState) -> KeyState {
+    match state {
+        winit::event::ElementState::Pressed => KeyState::Down,
+        winit::event::ElementState::Released => KeyState::Up,
     }
 }
 
@@ -56,6 +69,17 @@ pub fn from_winit_modifier_state(modifiers_state: ModifiersState) -> Modifiers {
     modifiers
 }
 
+/// Represent a held <kbd>Alt Graph</kbd> in `modifiers`.
+///
+/// Some platforms report <kbd>Alt Graph</kbd> as <kbd>Ctrl</kbd>+<kbd>Alt</kbd>, so these
+/// are replaced by [`Modifiers::ALT_GRAPH`], and characters composed with
+/// <kbd>Alt Graph</kbd> are not mistaken for shortcuts.
+pub fn with_alt_graph(mut modifiers: Modifiers) -> Modifiers {
+    modifiers.remove(Modifiers::CONTROL | Modifiers::ALT);
+    modifiers.insert(Modifiers::ALT_GRAPH);
+    modifiers
+}
+
 /// Convert a [`winit::keyboard::Key`] to a [`ui_events::keyboard::Key`].
 pub fn from_winit_key(winit_key: winit::keyboard::Key) -> Key {
     use winit::keyboard::Key as WK;
@@ -378,6 +402,19 @@ pub fn from_winit_key(winit_key: winit::keyboard::Key) -> Key {
     }
 }
 
+/// The native key code of a [`winit::keyboard::PhysicalKey`] that has no [`Code`].
+///
+/// [`from_winit_code`] maps these keys to [`Code::Unidentified`], so use this
+/// to tell them apart, for example to bind exotic keys.
+/// Returns `None` for identified keys, and when the native key code is unknown.
+pub fn native_key_code(physical_key: winit::keyboard::PhysicalKey) -> Option<NativeKeyCode> {
+    match physical_key {
+        winit::keyboard::PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+        | winit::keyboard::PhysicalKey::Code(_) => None,
+        winit::keyboard::PhysicalKey::Unidentified(native) => Some(native),
+    }
+}
+
 /// Convert a [`winit::keyboard::PhysicalKey`] to a [`ui_events::keyboard::Code`].
 pub fn from_winit_code(physical_key: winit::keyboard::PhysicalKey) -> Code {
     use winit::keyboard::KeyCode as KC;
@@ -589,3 +626,160 @@ pub fn from_winit_code(physical_key: winit::keyboard::PhysicalKey) -> Code {
         },
     }
 }
+
+/// The unmodified character of the key at `code` on a US QWERTY layout.
+///
+/// This identifies a key by its position, whatever the keyboard layout, for
+/// bindings like <kbd>W</kbd><kbd>A</kbd><kbd>S</kbd><kbd>D</kbd> which should
+/// stay in place on other layouts, and is the key of events in
+/// [raw keyboard mode](crate::WindowEventReducer::set_raw_keyboard).
+/// Returns `None` for keys which don't produce a character, and for the numpad,
+/// whose keys depend on <kbd>Num Lock</kbd> rather than the layout.
+pub fn us_layout_key(code: Code) -> Option<Key> {
+    let character = match code {
+        Code::KeyA => "a",
+        Code::KeyB => "b",
+        Code::KeyC => "c",
+        Code::KeyD => "d",
+        Code::KeyE => "e",
+        Code::KeyF => "f",
+        Code::KeyG => "g",
+        Code::KeyH => "h",
+        Code::KeyI => "i",
+        Code::KeyJ => "j",
+        Code::KeyK => "k",
+        Code::KeyL => "l",
+        Code::KeyM => "m",
+        Code::KeyN => "n",
+        Code::KeyO => "o",
+        Code::KeyP => "p",
+        Code::KeyQ => "q",
+        Code::KeyR => "r",
+        Code::KeyS => "s",
+        Code::KeyT => "t",
+        Code::KeyU => "u",
+        Code::KeyV => "v",
+        Code::KeyW => "w",
+        Code::KeyX => "x",
+        Code::KeyY => "y",
+        Code::KeyZ => "z",
+        Code::Digit0 => "0",
+        Code::Digit1 => "1",
+        Code::Digit2 => "2",
+        Code::Digit3 => "3",
+        Code::Digit4 => "4",
+        Code::Digit5 => "5",
+        Code::Digit6 => "6",
+        Code::Digit7 => "7",
+        Code::Digit8 => "8",
+        Code::Digit9 => "9",
+        Code::Backquote => "`",
+        Code::Minus => "-",
+        Code::Equal => "=",
+        Code::BracketLeft => "[",
+        Code::BracketRight => "]",
+        Code::Backslash => "\\",
+        Code::Semicolon => ";",
+        Code::Quote => "'",
+        Code::Comma => ",",
+        Code::Period => ".",
+        Code::Slash => "/",
+        Code::Space => " ",
+        _ => return None,
+    };
+    Some(Key::Character(String::from(character)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pressed keys are down, and released keys are up.
+    #[test]
+    fn key_state() {
+        use winit::event::ElementState;
+
+        assert_eq!(
+            from_winit_element_state(ElementState::Pressed),
+            KeyState::Down,
+            "a pressed key is down"
+        );
+        assert_eq!(
+            from_winit_element_state(ElementState::Released),
+            KeyState::Up,
+            "a released key is up"
+        );
+    }
+
+    /// Unidentified keys keep their native key code.
+    #[test]
+    fn native_key_code_round_trip() {
+        use winit::keyboard::{KeyCode, PhysicalKey};
+
+        let exotic = PhysicalKey::Unidentified(NativeKeyCode::Xkb(250));
+        assert_eq!(from_winit_code(exotic), Code::Unidentified, "no code");
+        assert_eq!(
+            native_key_code(exotic),
+            Some(NativeKeyCode::Xkb(250)),
+            "the native key code is kept"
+        );
+        assert_eq!(
+            native_key_code(PhysicalKey::Unidentified(NativeKeyCode::Unidentified)),
+            None,
+            "no native key code"
+        );
+        assert_eq!(
+            native_key_code(PhysicalKey::Code(KeyCode::KeyA)),
+            None,
+            "identified keys have a code"
+        );
+    }
+
+    /// Keys are identified by their position on a US layout.
+    #[test]
+    fn us_layout() {
+        assert_eq!(
+            us_layout_key(Code::KeyQ),
+            Some(Key::Character("q".into())),
+            "the key labelled A on AZERTY is q"
+        );
+        assert_eq!(
+            us_layout_key(Code::Semicolon),
+            Some(Key::Character(";".into())),
+            "punctuation is mapped"
+        );
+        assert_eq!(
+            us_layout_key(Code::Numpad1),
+            None,
+            "the numpad depends on Num Lock"
+        );
+        assert_eq!(us_layout_key(Code::Enter), None, "named keys aren't mapped");
+    }
+
+    /// <kbd>Alt Graph</kbd>+<kbd>Q</kbd> on a German layout is `@`, not <kbd>Ctrl</kbd>+<kbd>Alt</kbd>+`@`.
+    #[test]
+    fn alt_graph() {
+        let key = from_winit_key(winit::keyboard::Key::Character("@".into()));
+        assert_eq!(
+            key,
+            Key::Character("@".into()),
+            "the composed character is kept"
+        );
+
+        let modifiers = with_alt_graph(from_winit_modifier_state(
+            ModifiersState::CONTROL | ModifiersState::ALT,
+        ));
+        assert_eq!(
+            modifiers,
+            Modifiers::ALT_GRAPH,
+            "Alt Graph replaces Ctrl+Alt"
+        );
+
+        let modifiers = with_alt_graph(from_winit_modifier_state(ModifiersState::SHIFT));
+        assert_eq!(
+            modifiers,
+            Modifiers::ALT_GRAPH | Modifiers::SHIFT,
+            "other modifiers are kept"
+        );
+    }
+}
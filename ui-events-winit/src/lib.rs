@@ -6,7 +6,27 @@
 //!
 //! The primary entry point is [`WindowEventReducer`].
 //!
+//! ## Features
+//!
+//! - `std` (enabled by default): The default clock, file drop events, and `std::error::Error`
+//!   for `ReduceError`. Without it, create reducers with `WindowEventReducer::with_clock`.
+//!   [`winit`] itself needs the standard library, so this crate can't be used on targets
+//!   without it either way.
+//! - `test-util`: Builders for synthetic [`winit`] events, for tests and benchmarks.
+//! - `async`: A [`Stream`] adapter for translated events, in the `stream` module.
+//! - `gestures` (enabled by default): Recognition of gestures from touches, such as three finger taps.
+//! - `metrics`: Counters of the input processed by a reducer, in the `metrics` module.
+//!
+//! ## Supported `winit` versions
+//!
+//! This crate supports `winit` 0.30.10 and later 0.30 releases.
+//! Every [`WindowEvent`] variant translated here, including the gesture variants,
+//! is present throughout that range, and CI builds against both its oldest and newest
+//! releases, so a missing or changed variant fails to compile rather than being
+//! silently ignored. Other `winit` release series are not supported.
+//!
 //! [`ui-events`]: https://docs.rs/ui-events/
+//! [`Stream`]: https://docs.rs/futures-core/latest/futures_core/stream/trait.Stream.html
 
 // LINEBENDER LINT SET - lib.rs - v3
 // See https://linebender.org/wiki/canonical-lints/
@@ -20,356 +40,5068 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![no_std]
 
+pub mod clock;
+mod frame;
+#[cfg(feature = "gestures")]
+mod gesture;
 pub mod keyboard;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod pointer;
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod transform;
 
 extern crate alloc;
-use alloc::{vec, vec::Vec};
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
 
+use core::fmt;
+use core::mem::Discriminant;
+use core::time::Duration;
+
+#[cfg(feature = "std")]
 extern crate std;
-use std::time::Instant;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+use clock::{Clock, ReducerClock};
+use kurbo::{Affine, Point};
 
+pub use frame::FrameInput;
+#[cfg(feature = "gestures")]
+pub use gesture::{GestureStreams, ThreeFingerGestures};
+#[cfg(feature = "gestures")]
+use gesture::{Observation, ThreeFingerRecognizer};
+use transform::{DedupeMoves, EventTransform, PalmRejector, Transforms};
+#[cfg(feature = "gestures")]
+use ui_events::{gesture::GestureId, Tagged};
 use ui_events::{
-    keyboard::KeyboardEvent,
+    gesture::{GestureEvent, GesturePhase, PinchEvent, SmartZoomEvent},
+    keyboard::{Key, KeyboardEvent, Modifiers, NamedKey},
     pointer::{
-        PointerButtonEvent, PointerEvent, PointerId, PointerInfo, PointerScrollEvent, PointerState,
-        PointerType, PointerUpdate,
+        PersistentDeviceId, PointerButton, PointerButtonEvent, PointerEvent, PointerId,
+        PointerInfo, PointerOrientation, PointerScrollEvent, PointerState, PointerType,
+        PointerUpdate,
     },
     ScrollDelta,
 };
 use winit::{
-    event::{ElementState, Force, MouseScrollDelta, Touch, TouchPhase, WindowEvent},
-    keyboard::ModifiersState,
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{
+        ElementState, Force, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+    },
+    keyboard::{ModifiersState, NativeKeyCode},
+    window::WindowId,
 };
 
 /// Manages stateful transformations of winit [`WindowEvent`].
 ///
 /// Store a single instance of this per window, then call [`WindowEventReducer::reduce`]
 /// on each [`WindowEvent`] for that window.
-/// Use the [`WindowEventTranslation`] value to receive [`PointerEvent`]s and [`KeyboardEvent`]s.
+/// Use the [`WindowEventTranslation`] value to receive [`PointerEvent`]s, [`KeyboardEvent`]s,
+/// and [`GestureEvent`]s.
+///
+/// ```
+/// use ui_events::pointer::PointerEvent;
+/// use ui_events_winit::{clock::ManualClock, WindowEventReducer, WindowEventTranslation};
+/// use winit::{dpi::PhysicalPosition, event::{DeviceId, WindowEvent}};
+///
+/// // With the `std` feature, `WindowEventReducer::default()` reads the system clock.
+/// let mut reducer = WindowEventReducer::with_clock(ManualClock::new(1));
+/// let we = WindowEvent::CursorMoved {
+///     device_id: DeviceId::dummy(),
+///     position: PhysicalPosition::new(10.0, 20.0),
+/// };
+/// match reducer.reduce(&we) {
+///     Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) => {
+///         assert_eq!(update.current.position.x, 10.0);
+///     }
+///     Some(WindowEventTranslation::Keyboard(event)) => println!("{:?}", event.key),
+///     // The event isn't relevant to the reducer, or is another kind of translation.
+///     _ => {}
+/// }
+/// ```
+///
+/// The [`time`](PointerState::time) of pointer states is in nanoseconds, read from the
+/// [clock](WindowEventReducer::set_clock) of the reducer. By default, this is the time
+/// since the reducer was created, and is always positive, including for the first event.
+///
+/// Positions are in physical pixels relative to the window's surface, after the
+/// [coordinate transform](WindowEventReducer::set_coordinate_transform), so they stay
+/// valid when the window moves. Use [`WindowEventReducer::window_position`] to relate
+/// them to screen coordinates. They don't depend on the scale factor, so moves
+/// delivered during a live resize or a change of scale factor are never computed
+/// against a stale scale factor, and the window size from a [`WindowEvent::Resized`]
+/// applies to the events which follow it.
+///
+/// Styluses are reported by `winit` as touches, which carry no buttons, so pen
+/// events never have a [`PointerButton`] and a
+/// barrel button press can't be attributed to the pen. Platforms which turn barrel
+/// button presses into mouse input, such as Windows, deliver them as
+/// [`PointerButton::Secondary`]
+/// presses of the mouse.
+///
+/// Mouse buttons which [`pointer::try_from_winit_button`] can't map are reported
+/// with no button, rather than a catch-all button, and aren't tracked in
+/// [`PointerState::buttons`]. Since [`PointerButtonEvent::button_or_primary`] treats
+/// them as [`PointerButton::Primary`], check [`PointerButtonEvent::button`] of mouse
+/// events instead. Use [`WindowEventReducer::try_reduce`] to find out about unmapped buttons.
 ///
 /// This handles:
 ///  - [`ModifiersChanged`][`WindowEvent::ModifiersChanged`]
 ///  - [`KeyboardInput`][`WindowEvent::KeyboardInput`]
+///  - [`Ime`][`WindowEvent::Ime`]
 ///  - [`Touch`][`WindowEvent::Touch`]
 ///  - [`MouseInput`][`WindowEvent::MouseInput`]
 ///  - [`MouseWheel`][`WindowEvent::MouseWheel`]
 ///  - [`CursorMoved`][`WindowEvent::CursorMoved`]
 ///  - [`CursorEntered`][`WindowEvent::CursorEntered`]
 ///  - [`CursorLeft`][`WindowEvent::CursorLeft`]
-#[derive(Debug, Default)]
+///  - [`DoubleTapGesture`][`WindowEvent::DoubleTapGesture`]
+///  - [`PinchGesture`][`WindowEvent::PinchGesture`]
+///  - [`Resized`][`WindowEvent::Resized`]
+///  - [`Moved`][`WindowEvent::Moved`]
+///  - [`ScaleFactorChanged`][`WindowEvent::ScaleFactorChanged`]
+///  - [`HoveredFile`][`WindowEvent::HoveredFile`], [`DroppedFile`][`WindowEvent::DroppedFile`],
+///    and [`HoveredFileCancelled`][`WindowEvent::HoveredFileCancelled`], if
+///    [enabled](WindowEventReducer::set_file_drops)
+///
+/// [`RotationGesture`][`WindowEvent::RotationGesture`] and [`PanGesture`][`WindowEvent::PanGesture`]
+/// are not translated, but are accumulated with pinches for
+/// [`WindowEventReducer::current_gesture`].
+#[derive(Debug)]
 pub struct WindowEventReducer {
     /// State of modifiers.
     modifiers: ModifiersState,
+    /// Native key code of the last unidentified key.
+    last_native_key_code: Option<NativeKeyCode>,
+    /// Whether <kbd>Alt Graph</kbd> is held.
+    alt_graph: bool,
+    /// Whether an input method composition is in progress.
+    composing: bool,
+    /// Whether keyboard events are reported without text handling.
+    raw_keyboard: bool,
     /// State of the primary mouse pointer.
     primary_state: PointerState,
+    /// Position of the last primary pointer move since it entered.
+    primary_position: Option<PhysicalPosition<f64>>,
+    /// Position where the first of the held primary pointer buttons was pressed.
+    primary_down_position: Option<PhysicalPosition<f64>>,
+    /// Time when the first of the held primary pointer buttons was pressed.
+    primary_down_time: Option<u64>,
+    /// State of active touches, the first of which is the primary touch.
+    touches: Vec<TouchState>,
+    /// Generation of the last touch to start, if generations are enabled.
+    touch_generation: Option<u16>,
+    /// Whether to report the first touch of a multi-touch sequence as the mouse.
+    touch_mouse_emulation: bool,
+    /// Pointer ID and [`Touch::id`] of the touch reported as the mouse, if any.
+    emulated_touch: Option<(Option<PointerId>, u64)>,
+    /// Whether the last event made another touch the primary touch.
+    primary_touch_changed: bool,
+    /// Minimum pressure for a touch to be considered in contact.
+    touch_pressure_threshold: f32,
+    /// Pressure of touches without force information, if not the default.
+    default_touch_pressure: Option<f32>,
+    /// Transform applied to cursor and touch positions.
+    transform: Affine,
+    /// Palm rejection, if enabled.
+    palm_rejection: Option<PalmRejector>,
+    /// Jitter filter configuration, if enabled.
+    jitter_filter: Option<JitterFilter>,
+    /// Three finger gesture recognition, if enabled.
+    #[cfg(feature = "gestures")]
+    three_finger: Option<ThreeFingerRecognizer>,
+    /// Gesture ids of the translations of the event being reduced, in order.
+    #[cfg(feature = "gestures")]
+    gesture_tags: Vec<Option<GestureId>>,
+    /// Size of the window, if known.
+    window_size: Option<PhysicalSize<u32>>,
+    /// Whether the cursor entered, and its enter waits for the first position.
+    pending_enter: bool,
+    /// Whether a position of the cursor has been seen.
+    cursor_position_known: bool,
+    /// Position of the window on the screen, if known.
+    window_position: Option<PhysicalPosition<i32>>,
+    /// Scale factor of the window, if known.
+    scale_factor: Option<f64>,
+    /// Scroll configuration and state.
+    scroll: ScrollState,
+    /// Touchpad gesture in progress, if any.
+    gesture: Option<ActiveGesture>,
     /// Click and tap counter.
     counter: TapCounter,
-    /// First time an event was received..
-    first_instant: Option<Instant>,
+    /// Recent states of each pointer.
+    history: PointerHistory,
+    /// Stages run on each translation.
+    transforms: Transforms,
+    /// Metrics of the translations delivered.
+    #[cfg(feature = "metrics")]
+    metrics: metrics::Metrics,
+    /// The last translation delivered.
+    last_event: Option<WindowEventTranslation>,
+    /// Buffer for [`WindowEventReducer::reduce`].
+    scratch: Vec<WindowEventTranslation>,
+    /// Window that events are reduced for, if set.
+    window_id: Option<WindowId>,
+    /// Time base of [`PointerState::time`].
+    clock: ReducerClock,
+    /// Handling of timestamps which go backwards.
+    timestamp_policy: TimestampPolicy,
+    /// Which pointer has [`PointerId::PRIMARY`].
+    primary_pointer_policy: PrimaryPointerPolicy,
+    /// Time of the last event.
+    last_time: u64,
+    /// Whether to translate file drag and drop events.
+    #[cfg(feature = "std")]
+    file_drops: bool,
+    /// Whether to record the source of translations.
+    record_source: bool,
+    /// Source of the last translation, if recorded.
+    last_source: Option<Discriminant<WindowEvent>>,
+}
+
+/// A reducer reading the time of events from a [`StdClock`](clock::StdClock).
+#[cfg(feature = "std")]
+impl Default for WindowEventReducer {
+    fn default() -> Self {
+        Self::with_clock(clock::StdClock::default())
+    }
 }
 
 #[allow(clippy::cast_possible_truncation)]
 impl WindowEventReducer {
+    /// Create a reducer with the [`TapConfig`] `config`.
+    ///
+    /// This is the same as calling [`WindowEventReducer::set_tap_config`] on a default reducer.
+    #[cfg(feature = "std")]
+    pub fn with_tap_config(config: TapConfig) -> Self {
+        let mut reducer = Self::default();
+        reducer.set_tap_config(config);
+        reducer
+    }
+
+    /// Create a reducer which reads the time of events from `clock`.
+    ///
+    /// This is the same as calling [`WindowEventReducer::set_clock`] on a default reducer,
+    /// and is how a reducer is created without the `std` feature, which provides the
+    /// default clock.
+    pub fn with_clock(clock: impl Clock + Send + 'static) -> Self {
+        Self {
+            modifiers: ModifiersState::default(),
+            last_native_key_code: None,
+            alt_graph: false,
+            composing: false,
+            raw_keyboard: false,
+            primary_state: PointerState::default(),
+            primary_position: None,
+            primary_down_position: None,
+            primary_down_time: None,
+            touches: Vec::new(),
+            touch_generation: None,
+            touch_mouse_emulation: false,
+            emulated_touch: None,
+            primary_touch_changed: false,
+            touch_pressure_threshold: 0.0,
+            default_touch_pressure: None,
+            transform: Affine::IDENTITY,
+            palm_rejection: None,
+            jitter_filter: None,
+            #[cfg(feature = "gestures")]
+            three_finger: None,
+            #[cfg(feature = "gestures")]
+            gesture_tags: Vec::new(),
+            window_size: None,
+            pending_enter: false,
+            cursor_position_known: false,
+            window_position: None,
+            scale_factor: None,
+            scroll: ScrollState::default(),
+            gesture: None,
+            counter: TapCounter::default(),
+            history: PointerHistory::default(),
+            transforms: Transforms::default(),
+            #[cfg(feature = "metrics")]
+            metrics: metrics::Metrics::default(),
+            last_event: None,
+            scratch: Vec::new(),
+            window_id: None,
+            clock: ReducerClock::new(clock),
+            timestamp_policy: TimestampPolicy::default(),
+            primary_pointer_policy: PrimaryPointerPolicy::default(),
+            last_time: 0,
+            #[cfg(feature = "std")]
+            file_drops: false,
+            record_source: false,
+            last_source: None,
+        }
+    }
+
+    /// Set the [`Clock`] which the time of events is read from.
+    ///
+    /// The default is a [`StdClock`](clock::StdClock), measuring the time since the
+    /// reducer was created. Use another clock to share a time base with the rest of
+    /// an application, or a [`ManualClock`](clock::ManualClock) to control time in tests.
+    /// The clock is also used by [`WindowEventReducer::time_since_down`], but not for
+    /// events with a timestamp from [`WindowEventReducer::reduce_at`].
+    pub fn set_clock(&mut self, clock: impl Clock + Send + 'static) {
+        self.clock = ReducerClock::new(clock);
+    }
+
     /// Process a [`WindowEvent`].
+    ///
+    /// While an input method composition is in progress, [`KeyboardEvent`]s have
+    /// [`is_composing`](KeyboardEvent::is_composing) set, and should not be
+    /// handled as text input. While <kbd>Alt Graph</kbd> is held, their modifiers have
+    /// [`Modifiers::ALT_GRAPH`](ui_events::keyboard::Modifiers::ALT_GRAPH) instead
+    /// of <kbd>Ctrl</kbd>+<kbd>Alt</kbd>, see [`keyboard::with_alt_graph`].
+    ///
+    /// Translations are always delivered in the same order as the events
+    /// that produced them.
+    ///
+    /// # Dropped translations
+    ///
+    /// This returns only the first translation, and **drops the rest** when an event
+    /// produces several. The [`PointerEvent::Enter`] of the cursor is delivered on
+    /// [`CursorEntered`](WindowEvent::CursorEntered) with its last known position, so
+    /// hovers don't produce several, except for the first one, before any position is
+    /// known. Its enter waits for the first [`CursorMoved`](WindowEvent::CursorMoved), and
+    /// is returned instead of that move, which has the same state and no delta.
+    /// A touch which starts again without lifting is cancelled first, and this returns
+    /// its [`PointerEvent::Down`] without the [`PointerEvent::Cancel`].
+    /// Several translations are also produced by
+    /// [three finger gestures](WindowEventReducer::set_three_finger_gestures) and by
+    /// the stages added with [`WindowEventReducer::push_transform`].
+    ///
+    /// Use [`WindowEventReducer::reduce_into`] or [`WindowEventReducer::reduce_all`]
+    /// to receive all of them, unless only some kinds of events are handled and
+    /// these losses don't matter.
+    ///
+    /// The event is timestamped when it is processed, see [`WindowEventReducer::reduce_at`].
     pub fn reduce(&mut self, we: &WindowEvent) -> Option<WindowEventTranslation> {
-        const PRIMARY_MOUSE: PointerInfo = PointerInfo {
-            pointer_id: Some(PointerId::PRIMARY),
-            // TODO: Maybe transmute device.
-            persistent_device_id: None,
-            pointer_type: PointerType::Mouse,
-        };
+        self.reduce_at(we, self.clock.now_nanos())
+    }
 
-        let time = Instant::now()
-            .duration_since(*self.first_instant.get_or_insert_with(Instant::now))
-            .as_nanos() as u64;
+    /// Process a [`WindowEvent`] which happened at `timestamp`.
+    ///
+    /// This is the same as [`WindowEventReducer::reduce`], but the event has the
+    /// caller's `timestamp`, in nanoseconds, as its [`PointerState::time`],
+    /// instead of the time it is processed.
+    ///
+    /// Click and tap counting compares the times of presses and releases, so its
+    /// accuracy depends on the timestamp source. The time an event is processed
+    /// includes the delay before the event loop gets to it, which varies, so a
+    /// timestamp of when the platform received the event is more accurate where
+    /// one is available. Timestamps should increase from event to event, and be
+    /// positive, as a time of `0` is the default time of a state; see
+    /// [`WindowEventReducer::set_timestamp_policy`] for timestamps which go backwards.
+    ///
+    /// Mixing this with [`WindowEventReducer::reduce`] on the same reducer is only
+    /// meaningful if `timestamp` is measured from the creation of the reducer, as
+    /// that is the time base of [`WindowEventReducer::reduce`].
+    pub fn reduce_at(
+        &mut self,
+        we: &WindowEvent,
+        timestamp: u64,
+    ) -> Option<WindowEventTranslation> {
+        let mut out = core::mem::take(&mut self.scratch);
+        let own = self.reduce_into_at(we, timestamp, &mut out);
+        // Prefer the event's own translation to the cancel of a restarted touch.
+        let own = if own < out.len() { own } else { 0 };
+        let translation = (!out.is_empty()).then(|| out.swap_remove(own));
+        out.clear();
+        self.scratch = out;
+        translation
+    }
 
-        self.primary_state.time = time;
+    /// Process a [`WindowEvent`], pushing its translations onto `out`.
+    ///
+    /// This is the same as [`WindowEventReducer::reduce`], but delivers every
+    /// translation emitted by the stages added with [`WindowEventReducer::push_transform`],
+    /// in order.
+    ///
+    /// Translations are appended, and `out` is never cleared, so it can be owned by the
+    /// caller and reused across calls to avoid allocating, or collect the translations of
+    /// several events before they are handled. Since nothing borrows the reducer once this
+    /// returns, the translations can be handled while mutating the reducer.
+    pub fn reduce_into(&mut self, we: &WindowEvent, out: &mut Vec<WindowEventTranslation>) {
+        self.reduce_into_at(we, self.clock.now_nanos(), out);
+    }
 
-        match we {
-            WindowEvent::ModifiersChanged(m) => {
-                self.modifiers = m.state();
-                self.primary_state.modifiers = keyboard::from_winit_modifier_state(self.modifiers);
-                None
-            }
-            WindowEvent::KeyboardInput { event, .. } => Some(WindowEventTranslation::Keyboard(
-                keyboard::from_winit_keyboard_event(event.clone(), self.modifiers),
-            )),
-            WindowEvent::CursorEntered { .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Enter(PRIMARY_MOUSE),
-            )),
-            WindowEvent::CursorLeft { .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Leave(PRIMARY_MOUSE),
-            )),
-            WindowEvent::CursorMoved { position, .. } => {
-                self.primary_state.position = *position;
+    /// Process a [`WindowEvent`], returning all of its translations, in order.
+    ///
+    /// This is the same as [`WindowEventReducer::reduce_into`], but returns a new
+    /// [`Vec`], which is empty when the event has no translation. Use
+    /// [`WindowEventReducer::reduce_into`] to reuse a buffer instead.
+    pub fn reduce_all(&mut self, we: &WindowEvent) -> Vec<WindowEventTranslation> {
+        let mut out = Vec::new();
+        self.reduce_into(we, &mut out);
+        out
+    }
 
-                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    PointerEvent::Move(PointerUpdate {
-                        pointer: PRIMARY_MOUSE,
-                        current: self.primary_state.clone(),
-                        coalesced: vec![],
-                        predicted: vec![],
-                    }),
-                )))
-            }
-            WindowEvent::MouseInput {
-                state: ElementState::Pressed,
-                button,
-                ..
-            } => {
-                let button = pointer::try_from_winit_button(*button);
-                if let Some(button) = button {
-                    self.primary_state.buttons.insert(button);
-                }
+    /// Process a [`WindowEvent`], pushing its translations onto `out`, each tagged with
+    /// the [`GestureId`] of the three finger gesture it belongs to.
+    ///
+    /// This is the same as [`WindowEventReducer::reduce_into`], with the correlation
+    /// of [three finger gestures](WindowEventReducer::set_three_finger_gestures) and
+    /// their pointer events. An id is assigned when the first touch of a candidate
+    /// gesture goes down, and tags every pointer event of the touches taking part in it,
+    /// including the cancels of [`GestureStreams::GesturesOnly`], until the last of
+    /// them lifts. A recognized [`GestureEvent`] has the same id, in its tag and in the
+    /// event, and is delivered after the [`PointerEvent::Up`] of the last touch.
+    /// Ids are not reused while recognition stays enabled, and translations which
+    /// belong to no gesture are tagged with `None`.
+    #[cfg(feature = "gestures")]
+    pub fn reduce_into_tagged(
+        &mut self,
+        we: &WindowEvent,
+        out: &mut Vec<Tagged<WindowEventTranslation, Option<GestureId>>>,
+    ) {
+        let mut translations = core::mem::take(&mut self.scratch);
+        self.reduce_into(we, &mut translations);
+        out.extend(
+            translations
+                .drain(..)
+                .zip(self.gesture_tags.drain(..))
+                .map(|(translation, id)| Tagged::new(translation, id)),
+        );
+        self.scratch = translations;
+    }
 
-                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    PointerEvent::Down(PointerButtonEvent {
-                        pointer: PRIMARY_MOUSE,
-                        button,
-                        state: self.primary_state.clone(),
-                    }),
-                )))
+    /// Process the [`WindowEvent`]s of a frame, returning a summary of them.
+    ///
+    /// This is for immediate mode renderers, which poll the input state each frame
+    /// instead of handling each event. See [`FrameInput`].
+    pub fn reduce_frame(&mut self, events: &[WindowEvent]) -> FrameInput {
+        let mut frame = FrameInput::default();
+        let mut out = core::mem::take(&mut self.scratch);
+        for we in events {
+            self.reduce_into(we, &mut out);
+            for translation in out.drain(..) {
+                frame.record(translation);
             }
-            WindowEvent::MouseInput {
-                state: ElementState::Released,
-                button,
-                ..
-            } => {
-                let button = pointer::try_from_winit_button(*button);
-                if let Some(button) = button {
-                    self.primary_state.buttons.remove(button);
-                }
+        }
+        self.scratch = out;
+        frame.position = self.primary_position;
+        frame.buttons = self.primary_state.buttons;
+        frame.modifiers = self.current_modifiers();
+        frame
+    }
 
-                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    PointerEvent::Up(PointerButtonEvent {
-                        pointer: PRIMARY_MOUSE,
-                        button,
-                        state: self.primary_state.clone(),
-                    }),
-                )))
-            }
-            WindowEvent::MouseWheel { delta, .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Scroll(PointerScrollEvent {
-                    pointer: PRIMARY_MOUSE,
-                    delta: match *delta {
-                        MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
-                        MouseScrollDelta::PixelDelta(p) => ScrollDelta::PixelDelta(p),
-                    },
-                    state: self.primary_state.clone(),
-                }),
-            )),
-            WindowEvent::Touch(Touch {
-                phase,
-                id,
-                location,
-                force,
-                ..
-            }) => {
-                let pointer = PointerInfo {
-                    pointer_id: PointerId::new(id.saturating_add(1)),
-                    pointer_type: PointerType::Touch,
-                    persistent_device_id: None,
-                };
+    /// Returns `true` if `we` is translated, or updates the state of the reducer.
+    ///
+    /// This is the list of [`WindowEvent`] variants handled by the reducer, which
+    /// depends on its configuration, such as [`WindowEventReducer::set_file_drops`].
+    /// Events for which this is `false` can be handled elsewhere instead of
+    /// being passed to [`WindowEventReducer::reduce`], which would ignore them.
+    pub fn handles(&self, we: &WindowEvent) -> bool {
+        match we {
+            WindowEvent::ModifiersChanged(..)
+            | WindowEvent::KeyboardInput { .. }
+            | WindowEvent::Ime(..)
+            | WindowEvent::Touch(..)
+            | WindowEvent::MouseInput { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::CursorMoved { .. }
+            | WindowEvent::CursorEntered { .. }
+            | WindowEvent::CursorLeft { .. }
+            | WindowEvent::DoubleTapGesture { .. }
+            | WindowEvent::PinchGesture { .. }
+            | WindowEvent::RotationGesture { .. }
+            | WindowEvent::PanGesture { .. }
+            | WindowEvent::Resized(..)
+            | WindowEvent::Moved(..)
+            | WindowEvent::ScaleFactorChanged { .. } => true,
+            #[cfg(feature = "std")]
+            WindowEvent::HoveredFile(..)
+            | WindowEvent::DroppedFile(..)
+            | WindowEvent::HoveredFileCancelled => self.file_drops,
+            _ => false,
+        }
+    }
 
-                use TouchPhase::*;
+    /// Process a [`WindowEvent`], returning only scroll events.
+    ///
+    /// This is for consumers which only handle wheel and touchpad scrolling, like
+    /// a plot viewer. Only the events which affect the [`PointerState`] of a scroll
+    /// are processed: cursor, mouse button, and modifier events. Other events, such
+    /// as keyboard and touch events, are ignored without updating the reducer,
+    /// so don't mix this with [`WindowEventReducer::reduce`] on the same reducer.
+    pub fn reduce_scroll(&mut self, we: &WindowEvent) -> Option<PointerScrollEvent> {
+        if !matches!(
+            we,
+            WindowEvent::MouseWheel { .. }
+                | WindowEvent::CursorMoved { .. }
+                | WindowEvent::CursorEntered { .. }
+                | WindowEvent::CursorLeft { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::ModifiersChanged(..)
+        ) {
+            return None;
+        }
+        let mut out = core::mem::take(&mut self.scratch);
+        self.reduce_into(we, &mut out);
+        let scroll = out.drain(..).find_map(|translation| match translation {
+            WindowEventTranslation::Pointer(PointerEvent::Scroll(e)) => Some(e),
+            _ => None,
+        });
+        out.clear();
+        self.scratch = out;
+        scroll
+    }
 
-                let state = PointerState {
-                    time,
-                    position: *location,
-                    modifiers: self.primary_state.modifiers,
-                    pressure: if matches!(phase, Ended | Cancelled) {
-                        0.0
-                    } else {
-                        match force {
-                            Some(Force::Calibrated { force, .. }) => (force * 0.5) as f32,
-                            Some(Force::Normalized(q)) => *q as f32,
-                            _ => 0.5,
-                        }
-                    },
-                    ..Default::default()
-                };
+    /// Process a [`WindowEvent`] which happened at `time`, pushing its translations onto `out`.
+    ///
+    /// Returns the index in `out` of the first translation of the event itself,
+    /// which follows the cancel of a restarted touch.
+    fn reduce_into_at(
+        &mut self,
+        we: &WindowEvent,
+        time: u64,
+        out: &mut Vec<WindowEventTranslation>,
+    ) -> usize {
+        let time = if time < self.last_time {
+            match self.timestamp_policy {
+                TimestampPolicy::Clamp => self.last_time,
+                TimestampPolicy::ResetTaps => {
+                    self.counter.taps.clear();
+                    time
+                }
+            }
+        } else {
+            time
+        };
+        self.last_time = time;
+        #[cfg(feature = "gestures")]
+        self.gesture_tags.clear();
 
-                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    match phase {
-                        Started => PointerEvent::Down(PointerButtonEvent {
-                            pointer,
-                            button: None,
-                            state,
-                        }),
-                        Moved => PointerEvent::Move(PointerUpdate {
-                            pointer,
-                            current: state,
-                            coalesced: vec![],
-                            predicted: vec![],
-                        }),
-                        Cancelled => PointerEvent::Cancel(pointer),
-                        Ended => PointerEvent::Up(PointerButtonEvent {
-                            pointer,
-                            button: None,
-                            state,
-                        }),
-                    },
-                )))
+        let primary_touch = self.touches.first().map(|touch| touch.id);
+        let start = out.len();
+        if let WindowEvent::Touch(Touch {
+            phase: TouchPhase::Started,
+            id,
+            ..
+        }) = we
+        {
+            if let Some(index) = self.touches.iter().position(|touch| touch.id == *id) {
+                // The touch restarted without lifting, so its end was dropped.
+                let stale = self.touches.remove(index);
+                let cancel = PointerEvent::Cancel(stale.pointer_info());
+                let cancel = self.counter.attach_count(cancel);
+                self.deliver(cancel.into(), out);
+            }
+        }
+        let own = out.len();
+        let translation = self.translate(we, time);
+        let new_primary_touch = self.touches.first().map(|touch| touch.id);
+        self.primary_touch_changed = primary_touch.is_some()
+            && new_primary_touch.is_some()
+            && primary_touch != new_primary_touch;
+        if let Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) = &translation {
+            if self.pending_enter && update.pointer.pointer_type == PointerType::Mouse {
+                self.pending_enter = false;
+                let enter = PointerEvent::Enter(update.pointer, update.current.clone());
+                self.deliver(enter.into(), out);
             }
-            _ => None,
         }
+        if let Some(translation) = translation {
+            self.deliver(translation, out);
+        }
+        if self.record_source && out.len() > start {
+            self.last_source = Some(core::mem::discriminant(we));
+        }
+        own
     }
-}
 
-/// Result of [`WindowEventReducer::reduce`].
-#[derive(Debug)]
-pub enum WindowEventTranslation {
-    /// Resulting [`KeyboardEvent`].
-    Keyboard(KeyboardEvent),
-    /// Resulting [`PointerEvent`].
-    Pointer(PointerEvent),
-}
+    /// Run a translation through mouse emulation and the transforms, pushing the
+    /// results onto `out`.
+    fn run_transforms(
+        &mut self,
+        translation: WindowEventTranslation,
+        out: &mut Vec<WindowEventTranslation>,
+    ) {
+        let translation = match translation {
+            WindowEventTranslation::Pointer(e) if self.touch_mouse_emulation => {
+                self.emulate_mouse(e).into()
+            }
+            translation => translation,
+        };
+        self.transforms.run(translation, out);
+    }
 
-#[derive(Clone, Debug)]
-struct TapState {
-    /// Pointer ID used to attach tap counts to [`PointerEvent::Move`].
-    pointer_id: Option<PointerId>,
-    /// Nanosecond timestamp when the tap went Down.
-    down_time: u64,
-    /// Nanosecond timestamp when the tap went Up.
-    ///
-    /// Resets to `down_time` when tap goes Down.
-    up_time: u64,
-    /// The local tap count as of the last Down phase.
-    count: u8,
-    /// x coordinate.
-    x: f64,
-    /// y coordinate.
-    y: f64,
-}
+    /// Run a translation through palm rejection, gesture recognition and the
+    /// transforms, pushing the results onto `out` and recording them.
+    fn deliver(
+        &mut self,
+        translation: WindowEventTranslation,
+        out: &mut Vec<WindowEventTranslation>,
+    ) {
+        let start = out.len();
+        let translation = match &mut self.palm_rejection {
+            Some(palm_rejection) => {
+                let translation = palm_rejection.filter(translation);
+                if let WindowEventTranslation::Pointer(cancel @ PointerEvent::Cancel(..)) =
+                    &translation
+                {
+                    // The tap counter saw the up of a rejected touch, so it is cancelled there too.
+                    self.counter.attach_count(cancel.clone());
+                }
+                translation
+            }
+            None => translation,
+        };
+        #[cfg(feature = "gestures")]
+        {
+            let observation = match (&mut self.three_finger, &translation) {
+                (Some(recognizer), WindowEventTranslation::Pointer(e)) => recognizer.observe(e),
+                _ => Observation::default(),
+            };
+            for pointer in observation.cancelled {
+                self.run_transforms(PointerEvent::Cancel(pointer).into(), out);
+            }
+            if !observation.claimed {
+                self.run_transforms(translation, out);
+            }
+            if let Some(gesture) = observation.gesture {
+                self.transforms.run(gesture.into(), out);
+            }
+            self.gesture_tags
+                .resize(self.gesture_tags.len() + out.len() - start, observation.id);
+        }
+        #[cfg(not(feature = "gestures"))]
+        self.run_transforms(translation, out);
+        for translation in &out[start..] {
+            if let WindowEventTranslation::Pointer(e) = translation {
+                self.history.record(e);
+            }
+        }
+        if let Some(last) = out[start..].last() {
+            self.last_event = Some(last.clone());
+        }
+        #[cfg(feature = "metrics")]
+        {
+            let pointers = self.touches.len() + usize::from(!self.primary_state.buttons.is_empty());
+            for translation in &out[start..] {
+                self.metrics.record(translation, pointers);
+            }
+        }
+    }
 
-#[derive(Debug, Default)]
-struct TapCounter {
-    taps: Vec<TapState>,
-}
+    /// Report `e` as the mouse if it is from the emulated touch, see
+    /// [`WindowEventReducer::set_touch_mouse_emulation`].
+    fn emulate_mouse(&mut self, mut e: PointerEvent) -> PointerEvent {
+        let pointer = match &mut e {
+            PointerEvent::Down(PointerButtonEvent { pointer, .. })
+            | PointerEvent::Up(PointerButtonEvent { pointer, .. })
+            | PointerEvent::Move(PointerUpdate { pointer, .. })
+            | PointerEvent::Scroll(PointerScrollEvent { pointer, .. })
+            | PointerEvent::Enter(pointer, _)
+            | PointerEvent::Leave(pointer, _)
+            | PointerEvent::Cancel(pointer) => pointer,
+        };
+        if pointer.pointer_type == PointerType::Mouse {
+            return e;
+        }
+        let pointer_id = pointer.pointer_id;
+        if self.emulated_touch.is_none()
+            && self.touches.len() == 1
+            && matches!(e, PointerEvent::Down(..) | PointerEvent::Enter(..))
+        {
+            self.emulated_touch = self
+                .touches
+                .iter()
+                .find(|touch| touch.pointer_id() == pointer_id)
+                .map(|touch| (pointer_id, touch.id));
+        }
+        let Some((emulated_id, touch_id)) = self.emulated_touch else {
+            return e;
+        };
+        if emulated_id != pointer_id {
+            return e;
+        }
 
-impl TapCounter {
-    /// Enhance a [`PointerEvent`] with a `count`.
-    fn attach_count(&mut self, e: PointerEvent) -> PointerEvent {
+        let emulated = PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: PersistentDeviceId::new(touch_id.saturating_add(1)),
+            pointer_type: PointerType::Mouse,
+        };
+        let contact = self
+            .touches
+            .iter()
+            .any(|touch| touch.id == touch_id && touch.contact);
         match e {
             PointerEvent::Down(mut event) => {
-                let pointer_id = event.pointer.pointer_id;
-                let position = event.state.position;
-                let time = event.state.time;
-
-                if let Some(tap) =
-                    self.taps.iter_mut().find(|TapState { x, y, up_time, .. }| {
-                        let dx = (x - position.x).abs();
-                        let dy = (y - position.y).abs();
-                        (dx * dx + dy * dy).sqrt() < 4.0 && (up_time + 500_000_000) > time
-                    })
-                {
-                    let count = tap.count + 1;
-                    event.state.count = count;
-                    tap.count = count;
-                    tap.pointer_id = pointer_id;
-                    tap.down_time = time;
-                    tap.up_time = time;
-                    tap.x = position.x;
-                    tap.y = position.y;
-                } else {
-                    let s = TapState {
-                        pointer_id,
-                        down_time: time,
-                        up_time: time,
-                        count: 1,
-                        x: position.x,
-                        y: position.y,
-                    };
-                    self.taps.push(s);
-                    event.state.count = 1;
-                };
-                self.clear_expired(time);
+                event.pointer = emulated;
+                event.button = Some(PointerButton::Primary);
+                event.state.buttons.insert(PointerButton::Primary);
                 PointerEvent::Down(event)
             }
             PointerEvent::Up(mut event) => {
-                let p_id = event.pointer.pointer_id;
-                if let Some(tap) = self.taps.iter_mut().find(|state| state.pointer_id == p_id) {
-                    tap.up_time = event.state.time;
-                    event.state.count = tap.count;
-                }
+                self.emulated_touch = None;
+                event.pointer = emulated;
+                event.button = Some(PointerButton::Primary);
                 PointerEvent::Up(event)
             }
-            PointerEvent::Move(PointerUpdate {
-                pointer,
-                mut current,
-                mut coalesced,
-                mut predicted,
-            }) => {
-                if let Some(TapState { count, .. }) = self
-                    .taps
-                    .iter()
-                    .find(
-                        |TapState {
-                             pointer_id,
-                             down_time,
-                             up_time,
-                             ..
-                         }| {
-                            *pointer_id == pointer.pointer_id && down_time == up_time
-                        },
-                    )
-                    .cloned()
-                {
-                    current.count = count;
-                    for event in coalesced.iter_mut() {
-                        event.count = count;
-                    }
-                    for event in predicted.iter_mut() {
-                        event.count = count;
-                    }
-                    PointerEvent::Move(PointerUpdate {
-                        pointer,
-                        current,
-                        coalesced,
-                        predicted,
+            PointerEvent::Move(mut update) => {
+                update.pointer = emulated;
+                if contact {
+                    update.current.buttons.insert(PointerButton::Primary);
+                }
+                PointerEvent::Move(update)
+            }
+            PointerEvent::Leave(_, state) => {
+                self.emulated_touch = None;
+                PointerEvent::Leave(emulated, state)
+            }
+            PointerEvent::Cancel(_) => {
+                self.emulated_touch = None;
+                PointerEvent::Cancel(emulated)
+            }
+            PointerEvent::Enter(_, state) => PointerEvent::Enter(emulated, state),
+            e @ PointerEvent::Scroll(..) => e,
+        }
+    }
+
+    /// Cancel every active pointer, and reset the input state to neutral.
+    ///
+    /// This is for when input is interrupted, for example when a modal dialog opens
+    /// or the window loses focus. It returns a [`PointerEvent::Cancel`] for each
+    /// active touch, and for the mouse while any of its buttons are held. Held
+    /// buttons are cancelled rather than released, so there is no [`PointerEvent::Up`].
+    /// Click and tap sequences, gestures in progress, and modifiers are reset too.
+    pub fn cancel_all(&mut self) -> Vec<WindowEventTranslation> {
+        let mut cancels: Vec<PointerInfo> =
+            self.touches.iter().map(TouchState::pointer_info).collect();
+        if !self.primary_state.buttons.is_empty() {
+            cancels.push(self.mouse_pointer());
+        }
+
+        let mut out = Vec::new();
+        for pointer in cancels {
+            self.deliver(PointerEvent::Cancel(pointer).into(), &mut out);
+        }
+        self.touches.clear();
+        self.primary_state.buttons.clear();
+        self.primary_down_position = None;
+        self.primary_down_time = None;
+        self.counter.taps.clear();
+        self.modifiers = ModifiersState::empty();
+        self.primary_state.modifiers = Modifiers::empty();
+        self.alt_graph = false;
+        self.gesture = None;
+        self.emulated_touch = None;
+        out
+    }
+
+    /// Metrics of the translations delivered since the reducer was created,
+    /// or since [`WindowEventReducer::reset_metrics`].
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &metrics::Metrics {
+        &self.metrics
+    }
+
+    /// Reset the [`metrics`](WindowEventReducer::metrics).
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&mut self) {
+        self.metrics = metrics::Metrics::default();
+    }
+
+    /// The last translation delivered by [`WindowEventReducer::reduce`] or
+    /// [`WindowEventReducer::reduce_into`], if any.
+    ///
+    /// This is for consumers which poll for input each frame, rather than handling
+    /// each translation, along with [`WindowEventReducer::primary_pointer_state`].
+    pub fn last_event(&self) -> Option<&WindowEventTranslation> {
+        self.last_event.as_ref()
+    }
+
+    /// The position of the window on the screen, as of the last [`WindowEvent::Moved`].
+    ///
+    /// Add this to a pointer position to get its position on the screen, where
+    /// the platform reports window positions, and the coordinate transform is the identity.
+    pub fn window_position(&self) -> Option<PhysicalPosition<i32>> {
+        self.window_position
+    }
+
+    /// The scale factor of the window, as of the last [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// Positions, deltas, and pixel scroll deltas are physical, and are never converted
+    /// with this, so they stay consistent across a change. Use it to convert them to
+    /// logical pixels, for example with [`PhysicalPosition::to_logical`].
+    pub fn scale_factor(&self) -> Option<f64> {
+        self.scale_factor
+    }
+
+    /// Record a change of the scale factor of the window.
+    ///
+    /// A [`WindowEvent::ScaleFactorChanged`] can't be constructed outside of `winit`,
+    /// as its `InnerSizeWriter` can't be, so tests call this instead.
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = Some(scale_factor);
+    }
+
+    /// The current state of the primary mouse pointer.
+    ///
+    /// This includes its position, held buttons, and modifiers, as of the last
+    /// [`WindowEvent`] that was processed.
+    pub fn primary_pointer_state(&self) -> &PointerState {
+        &self.primary_state
+    }
+
+    /// The current state of the modifiers, as of the last [`WindowEvent`] that was processed.
+    ///
+    /// This is for handlers initialized while modifiers may already be held,
+    /// which would otherwise not know about them until the next [`KeyboardEvent`].
+    /// Like the modifiers of [`KeyboardEvent`]s, this has
+    /// [`Modifiers::ALT_GRAPH`](ui_events::keyboard::Modifiers::ALT_GRAPH) instead
+    /// of <kbd>Ctrl</kbd>+<kbd>Alt</kbd> while <kbd>Alt Graph</kbd> is held.
+    pub fn current_modifiers(&self) -> Modifiers {
+        let modifiers = keyboard::from_winit_modifier_state(self.modifiers);
+        if self.alt_graph {
+            keyboard::with_alt_graph(modifiers)
+        } else {
+            modifiers
+        }
+    }
+
+    /// The accumulated state of the touchpad gesture in progress, if any.
+    ///
+    /// A gesture begins with a [`WindowEvent::PinchGesture`], [`WindowEvent::RotationGesture`],
+    /// or [`WindowEvent::PanGesture`] while none is in progress, and lasts until
+    /// all of them have ended. This is for rendering live feedback of a gesture,
+    /// without accumulating its deltas.
+    pub fn current_gesture(&self) -> Option<GestureState> {
+        self.gesture.map(|gesture| gesture.state)
+    }
+
+    /// The [`PointerId`] of a touch with the platform ID `id` which is starting,
+    /// for when it isn't primary.
+    fn new_touch_pointer_id(&mut self, id: u64) -> Option<PointerId> {
+        let Some(generation) = &mut self.touch_generation else {
+            return PointerId::new(id.saturating_add(TOUCH_ID_OFFSET));
+        };
+        *generation = generation.wrapping_add(1);
+        let generation = *generation;
+        let raw = match id.checked_add(TOUCH_ID_OFFSET) {
+            Some(raw) if raw <= MAX_GENERATION_TOUCH_ID => raw,
+            // Masking the ID would make it collide, so use the highest free slot.
+            _ => (0..)
+                .map(|slot| MAX_GENERATION_TOUCH_ID - slot)
+                .find(|&raw| {
+                    !self.touches.iter().any(|touch| {
+                        touch.id != id
+                            && touch.secondary_id.and_then(|id| id.with_generation(0))
+                                == PointerId::new(raw)
                     })
-                } else {
+                })?,
+        };
+        PointerId::new(raw)?.with_generation(generation)
+    }
+
+    /// Accumulate a touchpad gesture delta into the [`current_gesture`](Self::current_gesture).
+    fn accumulate_gesture(
+        &mut self,
+        phase: TouchPhase,
+        active: fn(&mut ActiveGesture) -> &mut bool,
+        apply: impl FnOnce(&mut GestureState),
+    ) {
+        let gesture = self.gesture.get_or_insert(ActiveGesture {
+            state: GestureState {
+                scale: 1.0,
+                rotation: 0.0,
+                pan: PhysicalPosition::new(0.0, 0.0),
+            },
+            pinch: false,
+            rotation: false,
+            pan: false,
+        });
+        apply(&mut gesture.state);
+        *active(gesture) = matches!(phase, TouchPhase::Started | TouchPhase::Moved);
+        if !(gesture.pinch || gesture.rotation || gesture.pan) {
+            self.gesture = None;
+        }
+    }
+
+    /// The current state of the modifiers as `winit` reported it, for passing back to `winit`.
+    ///
+    /// This is the state of the last [`WindowEvent::ModifiersChanged`], which
+    /// [`current_modifiers`](Self::current_modifiers) converts.
+    pub fn winit_modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Process a [`WindowEvent`], reporting problems with it instead of degrading silently.
+    ///
+    /// The event is processed as by [`WindowEventReducer::reduce`], so the reducer state
+    /// stays consistent, but when it has a problem its translation is replaced by
+    /// a [`ReduceError`].
+    pub fn try_reduce(
+        &mut self,
+        we: &WindowEvent,
+    ) -> Result<Option<WindowEventTranslation>, ReduceError> {
+        let error = self.check(we);
+        let translation = self.reduce(we);
+        match error {
+            Some(error) => Err(error),
+            None => Ok(translation),
+        }
+    }
+
+    /// Find a problem with a [`WindowEvent`], for [`WindowEventReducer::try_reduce`].
+    fn check(&self, we: &WindowEvent) -> Option<ReduceError> {
+        use winit::keyboard::{Key, NativeKey, PhysicalKey};
+        match we {
+            WindowEvent::CursorMoved { position, .. }
+            | WindowEvent::Touch(Touch {
+                location: position, ..
+            }) if !is_finite(self.transform_position(*position)) => {
+                Some(ReduceError::NonFinitePosition)
+            }
+            WindowEvent::MouseInput { button, .. }
+                if pointer::try_from_winit_button(*button).is_none() =>
+            {
+                Some(ReduceError::UnmappedButton(*button))
+            }
+            WindowEvent::KeyboardInput { event, .. }
+                if matches!(
+                    (&event.logical_key, event.physical_key),
+                    (
+                        Key::Unidentified(NativeKey::Unidentified),
+                        PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+                    )
+                ) =>
+            {
+                Some(ReduceError::UnidentifiedKey)
+            }
+            _ => None,
+        }
+    }
+
+    /// Process a [`WindowEvent`], attributing the translation to the window set with
+    /// [`WindowEventReducer::set_window_id`].
+    ///
+    /// This is otherwise the same as [`WindowEventReducer::reduce`], and is intended
+    /// for merging the translations of several windows into a single stream.
+    ///
+    /// # Dropped translations
+    ///
+    /// Like [`WindowEventReducer::reduce`], this returns only the first translation, and
+    /// **drops the rest** when an event produces several. Use
+    /// [`WindowEventReducer::reduce_all_with_window_id`] to receive all of them.
+    pub fn reduce_with_window_id(
+        &mut self,
+        we: &WindowEvent,
+    ) -> Option<(Option<WindowId>, WindowEventTranslation)> {
+        self.reduce(we)
+            .map(|translation| (self.window_id, translation))
+    }
+
+    /// Process a [`WindowEvent`], returning all of its translations, in order, each
+    /// attributed to the window set with [`WindowEventReducer::set_window_id`].
+    ///
+    /// This is the same as [`WindowEventReducer::reduce_all`], and doesn't drop
+    /// translations like [`WindowEventReducer::reduce_with_window_id`].
+    pub fn reduce_all_with_window_id(
+        &mut self,
+        we: &WindowEvent,
+    ) -> Vec<(Option<WindowId>, WindowEventTranslation)> {
+        let window_id = self.window_id;
+        self.reduce_all(we)
+            .into_iter()
+            .map(|translation| (window_id, translation))
+            .collect()
+    }
+
+    /// Set the window that events are reduced for.
+    ///
+    /// See [`WindowEventReducer::reduce_with_window_id`].
+    /// The default is `None`.
+    pub fn set_window_id(&mut self, window_id: Option<WindowId>) {
+        self.window_id = window_id;
+    }
+
+    /// The window set with [`WindowEventReducer::set_window_id`], if any.
+    pub fn window_id(&self) -> Option<WindowId> {
+        self.window_id
+    }
+
+    /// Recent states of the pointer with `pointer_id`, oldest first.
+    ///
+    /// This includes the states of every [`PointerEvent`] with a state,
+    /// including coalesced states, up to the capacity set with
+    /// [`WindowEventReducer::set_pointer_history_capacity`].
+    ///
+    /// The history of a pointer is cleared when it is cancelled or leaves,
+    /// and the history of a touch which ended is discarded when another touch starts.
+    pub fn pointer_history(&self, pointer_id: PointerId) -> impl Iterator<Item = &PointerState> {
+        self.history
+            .pointers
+            .iter()
+            .find(move |history| history.pointer_id == pointer_id)
+            .into_iter()
+            .flat_map(|history| history.states.iter())
+    }
+
+    /// Set the number of states kept per pointer for [`WindowEventReducer::pointer_history`].
+    ///
+    /// The default is 8, and 0 disables the history.
+    pub fn set_pointer_history_capacity(&mut self, capacity: usize) {
+        self.history.capacity = capacity;
+        for history in &mut self.history.pointers {
+            while history.states.len() > capacity {
+                history.states.pop_front();
+            }
+        }
+        if capacity == 0 {
+            self.history.pointers.clear();
+        }
+    }
+
+    /// The click or tap count of the current sequence of `pointer_id`, if it is still live.
+    ///
+    /// A sequence is live while a further click at `now` would continue it, as long
+    /// as that click lands close enough to the last one. `now` has the same time base
+    /// as [`PointerState::time`].
+    pub fn is_sequence_live(&self, pointer_id: PointerId, now: u64) -> Option<u8> {
+        self.counter.is_sequence_live(pointer_id, now)
+    }
+
+    /// The pointer ID of the primary touch, if any touch is active.
+    ///
+    /// The first touch to start while no other touch is active is the primary touch.
+    /// When it ends or is cancelled while other touches are still active, the earliest
+    /// of those becomes the primary touch, see [`WindowEventReducer::primary_touch_changed`].
+    pub fn primary_touch(&self) -> Option<PointerId> {
+        self.touches.first().and_then(TouchState::pointer_id)
+    }
+
+    /// Set whether touches have a new [generation](PointerId::generation) each time they start.
+    ///
+    /// Platforms reuse the IDs of touches which have lifted, so the [`PointerId`]s of
+    /// touches are recycled too. With generations, each touch gets a different
+    /// [`PointerId`], so a stale [`PointerId`] of a lifted touch is never confused
+    /// with the touch which reused its platform ID.
+    ///
+    /// The generation takes the highest 16 bits of the [`PointerId`], see
+    /// [`PointerId::with_generation`]. Platform IDs which don't fit in the rest, such
+    /// as those derived from addresses, are given a compact ID for as long as the
+    /// touch is active instead, so they never collide.
+    ///
+    /// This is disabled by default, so the [`PointerId`]s of touches are their full
+    /// platform IDs, offset past the reserved IDs.
+    pub fn set_pointer_id_generations(&mut self, enabled: bool) {
+        self.touch_generation = enabled.then_some(self.touch_generation.unwrap_or(0));
+    }
+
+    /// Returns `true` if the last call to [`WindowEventReducer::reduce`] made another
+    /// touch the primary touch.
+    ///
+    /// This is the case when the primary touch ends or is cancelled while other touches
+    /// are still active, so that consumers tracking the primary touch can switch to
+    /// [`WindowEventReducer::primary_touch`].
+    pub fn primary_touch_changed(&self) -> bool {
+        self.primary_touch_changed
+    }
+
+    /// The native key code of the last [`KeyboardEvent`], if its key is unidentified.
+    ///
+    /// Such keys have a [`code`](KeyboardEvent::code) of
+    /// [`Code::Unidentified`](ui_events::keyboard::Code::Unidentified), so use this
+    /// to tell them apart. See [`keyboard::native_key_code`].
+    pub fn last_native_key_code(&self) -> Option<NativeKeyCode> {
+        self.last_native_key_code
+    }
+
+    /// Set the click or tap count after which counts wrap back to 1.
+    ///
+    /// With a maximum of 3, a quadruple click is counted like a single click,
+    /// as is common in text editors. `None` or `Some(0)` doesn't wrap,
+    /// which is the default.
+    pub fn set_max_tap_count(&mut self, max_count: Option<u8>) {
+        self.counter.max_count = max_count;
+    }
+
+    /// Set the [`TapConfig`], the thresholds for a click or tap to continue a sequence.
+    ///
+    /// The default is [`TapConfig::default`].
+    pub fn set_tap_config(&mut self, config: TapConfig) {
+        self.counter.config = config;
+    }
+
+    /// Set whether to count clicks and taps.
+    ///
+    /// When disabled, the [`count`](PointerState::count) of every pointer state is `0`,
+    /// and no work is spent on counting, for consumers which count clicks themselves.
+    /// This is enabled by default.
+    pub fn set_tap_counting(&mut self, enabled: bool) {
+        self.counter.disabled = !enabled;
+        if !enabled {
+            self.counter.taps.clear();
+        }
+    }
+
+    /// Set whether to record the [`WindowEvent`] that produced each translation.
+    ///
+    /// This is off by default, and is intended for troubleshooting.
+    /// See [`WindowEventReducer::last_source`].
+    pub fn set_record_source(&mut self, record_source: bool) {
+        self.record_source = record_source;
+        if !record_source {
+            self.last_source = None;
+        }
+    }
+
+    /// The discriminant of the [`WindowEvent`] that produced the last translation.
+    ///
+    /// This is always `None` unless enabled with [`WindowEventReducer::set_record_source`].
+    /// Compare it with [`core::mem::discriminant`] of a [`WindowEvent`] to correlate
+    /// a translation with its input.
+    pub fn last_source(&self) -> Option<Discriminant<WindowEvent>> {
+        self.last_source
+    }
+
+    /// Set whether to drop pointer moves which don't change the pointer state.
+    ///
+    /// Some platforms repeatedly send the same cursor position. When enabled,
+    /// a [`PointerEvent::Move`] with the same position, buttons, and modifiers
+    /// as the last one of that pointer is dropped, see [`DedupeMoves`].
+    /// This runs before the stages added with [`WindowEventReducer::push_transform`].
+    ///
+    /// This is off by default, so that every move is delivered for consumers
+    /// which rely on their timing.
+    pub fn set_dedupe_moves(&mut self, dedupe_moves: bool) {
+        self.transforms.dedupe_moves = dedupe_moves.then(DedupeMoves::default);
+    }
+
+    /// Add a stage which is run on every translation, after the stages added before it.
+    ///
+    /// When stages may emit several translations for one [`WindowEvent`],
+    /// use [`WindowEventReducer::reduce_into`] to receive all of them.
+    pub fn push_transform(&mut self, transform: impl EventTransform + Send + 'static) {
+        self.transforms.stages.push(Box::new(transform));
+    }
+
+    /// Remove the stages added with [`WindowEventReducer::push_transform`].
+    pub fn clear_transforms(&mut self) {
+        self.transforms.stages.clear();
+    }
+
+    /// Set the minimum pressure for a touch to be considered in contact.
+    ///
+    /// Touches with a reported force below `threshold` are translated to hovering
+    /// [`PointerEvent::Move`]s, and only go [`Down`][PointerEvent::Down] once their pressure
+    /// reaches `threshold`. A touch that ends without reaching it is translated to
+    /// [`PointerEvent::Leave`]. Hovering states have a pressure of `0.0`.
+    ///
+    /// Touches reporting an altitude are styluses, with [`PointerType::Pen`].
+    /// A stylus which starts below `threshold` is translated to [`PointerEvent::Enter`],
+    /// so that it can be used for a hover cursor.
+    ///
+    /// Touches without force information are always considered in contact.
+    /// The default is `0.0`, so that every touch is in contact.
+    pub fn set_touch_pressure_threshold(&mut self, threshold: f32) {
+        self.touch_pressure_threshold = threshold;
+    }
+
+    /// Set the pressure of touches in contact when the platform doesn't report their force.
+    ///
+    /// The default is `0.5`. Touches which have ended have a pressure of `0.0`
+    /// whether or not their force is reported.
+    pub fn set_default_touch_pressure(&mut self, pressure: f32) {
+        self.default_touch_pressure = Some(pressure);
+    }
+
+    /// Set the transform applied to cursor and touch positions.
+    ///
+    /// This is applied before positions are stored in [`PointerState`],
+    /// so click and tap counting operates in the transformed space.
+    /// Scroll deltas are not transformed.
+    ///
+    /// The default is [`Affine::IDENTITY`].
+    pub fn set_coordinate_transform(&mut self, transform: Affine) {
+        self.transform = transform;
+    }
+
+    /// Set whether to report keyboard events without text handling, for games.
+    ///
+    /// In raw mode, every key press and release is reported as `winit` delivers it,
+    /// and is never marked as [composing](KeyboardEvent::is_composing), even while
+    /// an input method composition is in progress. <kbd>Alt Graph</kbd> is reported as
+    /// the modifiers that the platform reports, instead of
+    /// [`Modifiers::ALT_GRAPH`](ui_events::keyboard::Modifiers::ALT_GRAPH), and the key
+    /// is not replaced by the composed character. The [`key`](KeyboardEvent::key) of
+    /// character keys is derived from their [`code`](KeyboardEvent::code), which is
+    /// the physical key, as the character on a US layout, see [`keyboard::us_layout_key`],
+    /// so bindings don't depend on the keyboard layout. Other keys keep their logical key.
+    ///
+    /// This is disabled by default, which is right for text input.
+    pub fn set_raw_keyboard(&mut self, enabled: bool) {
+        self.raw_keyboard = enabled;
+    }
+
+    /// Set the multiplier applied to both axes of scroll deltas.
+    ///
+    /// This scales line and pixel deltas, but not [`notches`](PointerScrollEvent::notches)
+    /// or the [`raw_delta`](PointerScrollEvent::raw_delta).
+    /// The default is `1.0`.
+    pub fn set_scroll_sensitivity(&mut self, sensitivity: f64) {
+        self.scroll.sensitivity = sensitivity;
+    }
+
+    /// Set the multiplier applied to the horizontal axis of line deltas, from tilt wheels.
+    ///
+    /// Mice with a tilt wheel report horizontal line deltas, while touchpads usually
+    /// report pixel deltas, so this tells them apart: it doesn't affect pixel deltas.
+    /// It applies in addition to the [scroll sensitivity](Self::set_scroll_sensitivity),
+    /// with the same exceptions. Use a negative multiplier to reverse the direction
+    /// of tilting, or `0.0` to ignore it, for example to map it to another action
+    /// using the [`raw_delta`](PointerScrollEvent::raw_delta).
+    /// The default is `1.0`.
+    pub fn set_wheel_tilt_sensitivity(&mut self, sensitivity: f64) {
+        self.scroll.tilt_sensitivity = sensitivity;
+    }
+
+    /// Set the [`PalmRejection`] configuration, or `None` to disable it.
+    ///
+    /// This is disabled by default.
+    /// The right and bottom edges are only considered once the window size is known
+    /// from a [`WindowEvent::Resized`]. The margin is measured on the positions of
+    /// translations, after the [coordinate transform](Self::set_coordinate_transform).
+    /// See [`PalmRejector`], which this runs before gesture
+    /// recognition and mouse emulation.
+    pub fn set_palm_rejection(&mut self, palm_rejection: Option<PalmRejection>) {
+        self.palm_rejection =
+            palm_rejection.map(|config| PalmRejector::new(config, self.window_size));
+    }
+
+    /// Set whether to translate file drag and drop events into [`FileDropEvent`]s.
+    ///
+    /// This is disabled by default, so these events are ignored.
+    #[cfg(feature = "std")]
+    pub fn set_file_drops(&mut self, enabled: bool) {
+        self.file_drops = enabled;
+    }
+
+    /// Set whether to report touches as the mouse, for widgets which only handle the mouse.
+    ///
+    /// While enabled, the first touch of a multi-touch sequence is reported as the mouse,
+    /// with [`PointerId::PRIMARY`] and [`PointerType::Mouse`]. Its contact presses and
+    /// releases [`PointerButton::Primary`], and is held in the buttons of its moves.
+    /// The [`Touch::id`] of the touch, plus one, is kept in its
+    /// [`persistent_device_id`](PointerInfo::persistent_device_id), so consumers can tell
+    /// it from the real mouse. Only that touch is emulated, until it ends: other touches
+    /// are reported as touches, even if the emulated touch ends first, so that
+    /// the mouse doesn't jump between them.
+    ///
+    /// This is disabled by default.
+    pub fn set_touch_mouse_emulation(&mut self, enabled: bool) {
+        self.touch_mouse_emulation = enabled;
+        if !enabled {
+            self.emulated_touch = None;
+        }
+    }
+
+    /// Set the [`PrimaryPointerPolicy`], which decides which pointer has [`PointerId::PRIMARY`].
+    ///
+    /// The default is [`PrimaryPointerPolicy::MousePreferred`].
+    pub fn set_primary_pointer_policy(&mut self, policy: PrimaryPointerPolicy) {
+        self.primary_pointer_policy = policy;
+    }
+
+    /// The [`PointerInfo`] of the mouse, which depends on the [`PrimaryPointerPolicy`].
+    fn mouse_pointer(&self) -> PointerInfo {
+        if self.touches.iter().any(|touch| touch.primary) {
+            PointerInfo {
+                pointer_id: PointerId::new(SECONDARY_MOUSE_ID),
+                ..PRIMARY_MOUSE
+            }
+        } else {
+            PRIMARY_MOUSE
+        }
+    }
+
+    /// Set the [`TimestampPolicy`] for timestamps which go backwards.
+    ///
+    /// The default is [`TimestampPolicy::Clamp`].
+    pub fn set_timestamp_policy(&mut self, policy: TimestampPolicy) {
+        self.timestamp_policy = policy;
+    }
+
+    /// Set the [`JitterFilter`] configuration, or `None` to disable it.
+    ///
+    /// This is applied to cursor and touch positions before they are stored in
+    /// [`PointerState`], so click and tap counting uses the filtered positions.
+    /// This is disabled by default.
+    pub fn set_jitter_filter(&mut self, jitter_filter: Option<JitterFilter>) {
+        self.jitter_filter = jitter_filter;
+    }
+
+    /// Set the [`ThreeFingerGestures`] configuration, or `None` to disable recognition.
+    ///
+    /// When enabled, the [`PointerEvent::Up`] of the last finger of a recognized
+    /// gesture is followed by a [`GestureEvent::ThreeFingerTap`] or
+    /// [`GestureEvent::ThreeFingerSwipe`], so use [`WindowEventReducer::reduce_into`]
+    /// to receive both, or [`WindowEventReducer::reduce_into_tagged`] to also correlate
+    /// them. Whether the pointer events are delivered at all is set by
+    /// [`ThreeFingerGestures::streams`]. This is disabled by default.
+    #[cfg(feature = "gestures")]
+    pub fn set_three_finger_gestures(&mut self, config: Option<ThreeFingerGestures>) {
+        match (&mut self.three_finger, config) {
+            (Some(recognizer), Some(config)) => recognizer.config = config,
+            (recognizer, config) => {
+                *recognizer = config.map(ThreeFingerRecognizer::new);
+            }
+        }
+    }
+
+    /// How long the pointer `pointer_id` has been down, if it is.
+    ///
+    /// For the mouse, this is since the first of the held buttons was pressed, and for
+    /// a touch or pen, since it reached contact. This is `None` while the pointer is up,
+    /// or hovering. It is measured with the same clock as [`PointerState::time`],
+    /// and is for press and hold interactions, like showing a context menu.
+    pub fn time_since_down(&self, pointer_id: PointerId) -> Option<Duration> {
+        let down_time = if Some(pointer_id) == self.mouse_pointer().pointer_id
+            && self.primary_down_time.is_some()
+        {
+            self.primary_down_time
+        } else {
+            self.touches
+                .iter()
+                .find(|touch| touch.pointer_id() == Some(pointer_id))
+                .and_then(|touch| touch.down_time)
+        }?;
+        Some(Duration::from_nanos(
+            self.clock.now_nanos().saturating_sub(down_time),
+        ))
+    }
+
+    /// The number of touches currently active, whether in contact or hovering.
+    pub fn active_touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    /// Apply the coordinate transform to a position.
+    fn transform_position(&self, position: PhysicalPosition<f64>) -> PhysicalPosition<f64> {
+        let Point { x, y } = self.transform * Point::new(position.x, position.y);
+        PhysicalPosition::new(x, y)
+    }
+
+    /// Apply the jitter filter to a move from `previous` to `position`.
+    fn filter_jitter(
+        &self,
+        previous: Option<PhysicalPosition<f64>>,
+        position: PhysicalPosition<f64>,
+    ) -> PhysicalPosition<f64> {
+        match (self.jitter_filter, previous) {
+            (Some(filter), Some(previous))
+                if (position.x - previous.x).hypot(position.y - previous.y) < filter.amplitude =>
+            {
+                PhysicalPosition::new(
+                    previous.x + (position.x - previous.x) * filter.smoothing,
+                    previous.y + (position.y - previous.y) * filter.smoothing,
+                )
+            }
+            _ => position,
+        }
+    }
+
+    /// Apply the keyboard state to a converted key event, updating it.
+    ///
+    /// `text` is the text which `winit` resolved for the key.
+    /// This is separate from [`WindowEventReducer::translate`] because `winit` key
+    /// events can't be constructed outside of `winit`, so it is what tests drive.
+    fn translate_key(&mut self, mut event: KeyboardEvent, text: Option<&str>) -> KeyboardEvent {
+        if event.key == Key::Named(NamedKey::AltGraph) {
+            self.alt_graph = event.state.is_down();
+        }
+        if self.raw_keyboard {
+            if let Some(key) = keyboard::us_layout_key(event.code) {
+                event.key = key;
+            }
+            return event;
+        }
+        if self.alt_graph {
+            event.modifiers = keyboard::with_alt_graph(event.modifiers);
+            // The resolved text is the character composed with AltGr.
+            if let (Key::Character(_), Some(text)) = (&event.key, text) {
+                event.key = Key::Character(text.into());
+            }
+        }
+        // Keys pressed during composition are consumed by the input method.
+        event.is_composing = self.composing;
+        event
+    }
+
+    /// Translate a [`WindowEvent`], updating the reducer state.
+    fn translate(&mut self, we: &WindowEvent, time: u64) -> Option<WindowEventTranslation> {
+        self.primary_state.time = time;
+        let mouse = self.mouse_pointer();
+
+        match we {
+            WindowEvent::Resized(size) => {
+                self.window_size = Some(*size);
+                if let Some(palm_rejection) = &mut self.palm_rejection {
+                    palm_rejection.set_window_size(self.window_size);
+                }
+                None
+            }
+            WindowEvent::Moved(position) => {
+                // Positions are window relative, so they don't need updating.
+                self.window_position = Some(*position);
+                None
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                // Positions are physical, so they don't need updating either.
+                self.set_scale_factor(*scale_factor);
+                None
+            }
+            WindowEvent::ModifiersChanged(m) => {
+                self.modifiers = m.state();
+                self.primary_state.modifiers = keyboard::from_winit_modifier_state(self.modifiers);
+                None
+            }
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => {
+                self.last_native_key_code = keyboard::native_key_code(key_event.physical_key);
+                let event = keyboard::reduce_key_event(key_event, self.modifiers);
+                Some(WindowEventTranslation::Keyboard(
+                    self.translate_key(event, key_event.text.as_deref()),
+                ))
+            }
+            WindowEvent::Ime(ime) => {
+                self.composing = matches!(ime, Ime::Preedit(text, _) if !text.is_empty());
+                None
+            }
+            WindowEvent::CursorEntered { .. } => {
+                self.primary_position = None;
+                if !self.cursor_position_known {
+                    // There is no position yet, so the first move delivers the enter.
+                    self.pending_enter = true;
+                    return None;
+                }
+                Some(WindowEventTranslation::Pointer(PointerEvent::Enter(
+                    mouse,
+                    self.primary_state.clone(),
+                )))
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.primary_position = None;
+                if core::mem::take(&mut self.pending_enter) {
+                    // The cursor left before its enter was delivered.
+                    return None;
+                }
+                Some(WindowEventTranslation::Pointer(PointerEvent::Leave(
+                    mouse,
+                    self.primary_state.clone(),
+                )))
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = self.transform_position(*position);
+                if !is_finite(position) {
+                    // Some drivers deliver garbage, drop it rather than poisoning the state.
+                    return None;
+                }
+                let position = self.filter_jitter(self.primary_position, position);
+                self.primary_state.position = position;
+                self.cursor_position_known = true;
+
+                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
                     PointerEvent::Move(PointerUpdate {
-                        pointer,
-                        current,
-                        coalesced,
-                        predicted,
-                    })
+                        pointer: mouse,
+                        current: self.primary_state.clone(),
+                        previous_position: self.primary_position.replace(position),
+                        down_position: self.primary_down_position,
+                        velocity: None,
+                        coalesced: vec![],
+                        predicted: vec![],
+                    }),
+                )))
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button,
+                ..
+            } => {
+                // Buttons without a representation have no button, and aren't
+                // held. See `try_from_winit_button` for why there is no catch-all.
+                let button = pointer::try_from_winit_button(*button);
+                if let Some(button) = button {
+                    if self.primary_state.buttons.is_empty() {
+                        self.primary_down_position = Some(self.primary_state.position);
+                        self.primary_down_time = Some(time);
+                    }
+                    self.primary_state.buttons.insert(button);
                 }
+
+                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
+                    PointerEvent::Down(PointerButtonEvent {
+                        pointer: mouse,
+                        button,
+                        state: self.primary_state.clone(),
+                    }),
+                )))
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button,
+                ..
+            } => {
+                let button = pointer::try_from_winit_button(*button);
+                if let Some(button) = button {
+                    self.primary_state.buttons.remove(button);
+                    if self.primary_state.buttons.is_empty() {
+                        self.primary_down_position = None;
+                        self.primary_down_time = None;
+                    }
+                }
+
+                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
+                    PointerEvent::Up(PointerButtonEvent {
+                        pointer: mouse,
+                        button,
+                        state: self.primary_state.clone(),
+                    }),
+                )))
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let notches = match *delta {
+                    MouseScrollDelta::LineDelta(x, y) => {
+                        // Accumulate fractional lines from high resolution wheels,
+                        // and report whole notches, truncating toward zero.
+                        // A reversal discards the remainder of the old direction.
+                        let (rx, ry) = &mut self.scroll.notch_remainder;
+                        *rx = if *rx * x < 0.0 { x } else { *rx + x };
+                        *ry = if *ry * y < 0.0 { y } else { *ry + y };
+                        let notches = (*rx as i32, *ry as i32);
+                        *rx -= notches.0 as f32;
+                        *ry -= notches.1 as f32;
+                        Some(notches)
+                    }
+                    MouseScrollDelta::PixelDelta(..) => {
+                        self.scroll.notch_remainder = (0.0, 0.0);
+                        None
+                    }
+                };
+                // Pixel deltas are in physical pixels, like cursor positions, so
+                // they are not rescaled, and a scale factor change mid-scroll
+                // doesn't change the amount scrolled.
+                let raw_delta = match *delta {
+                    MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
+                    MouseScrollDelta::PixelDelta(p) => ScrollDelta::PixelDelta(p),
+                };
+                Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(
+                    PointerScrollEvent {
+                        pointer: mouse,
+                        delta: self.scroll.scale(raw_delta),
+                        raw_delta,
+                        notches,
+                        state: self.primary_state.clone(),
+                    },
+                )))
+            }
+            WindowEvent::Touch(Touch {
+                phase,
+                id,
+                location: raw_location,
+                force,
+                ..
+            }) => {
+                let tracked = self.touches.iter().find(|touch| touch.id == *id);
+                let previous = tracked.map(|touch| touch.position);
+                let secondary_id = match tracked {
+                    Some(touch) if *phase != TouchPhase::Started => touch.secondary_id,
+                    _ => self.new_touch_pointer_id(*id),
+                };
+                // Found again, as allocating an ID borrows the reducer mutably.
+                let tracked = self.touches.iter().find(|touch| touch.id == *id);
+                let primary = match tracked {
+                    Some(touch) if *phase != TouchPhase::Started => touch.primary,
+                    _ => {
+                        matches!(phase, TouchPhase::Started | TouchPhase::Moved)
+                            && self.primary_pointer_policy == PrimaryPointerPolicy::FirstContact
+                            && self.primary_state.buttons.is_empty()
+                            && !self
+                                .touches
+                                .iter()
+                                .any(|touch| touch.primary && touch.id != *id)
+                    }
+                };
+                let mut location = self.transform_position(*raw_location);
+                if !is_finite(location) {
+                    // Some drivers deliver garbage, fall back to the last known position
+                    // so that the phase is not lost, or drop the event if there is none.
+                    location = previous?;
+                }
+                if *phase != TouchPhase::Started {
+                    location = self.filter_jitter(previous, location);
+                }
+                // Only styluses report an altitude.
+                let altitude = match force {
+                    Some(Force::Calibrated {
+                        altitude_angle: Some(altitude),
+                        ..
+                    }) => Some(*altitude as f32),
+                    _ => None,
+                };
+                let pointer = PointerInfo {
+                    pointer_id: if primary {
+                        Some(PointerId::PRIMARY)
+                    } else {
+                        secondary_id
+                    },
+                    pointer_type: if altitude.is_some() {
+                        PointerType::Pen
+                    } else {
+                        PointerType::Touch
+                    },
+                    persistent_device_id: None,
+                };
+
+                use TouchPhase::*;
+
+                let mut state = PointerState {
+                    time,
+                    position: location,
+                    // The modifiers come from the keyboard, not from the mouse state.
+                    modifiers: keyboard::from_winit_modifier_state(self.modifiers),
+                    pressure: if matches!(phase, Ended | Cancelled) {
+                        0.0
+                    } else {
+                        match force {
+                            Some(Force::Calibrated {
+                                force,
+                                altitude_angle,
+                                ..
+                            }) => calibrated_pressure(*force, *altitude_angle),
+                            Some(Force::Normalized(q)) => *q as f32,
+                            _ => self
+                                .default_touch_pressure
+                                .unwrap_or(DEFAULT_TOUCH_PRESSURE),
+                        }
+                    },
+                    orientation: PointerOrientation {
+                        altitude: altitude.unwrap_or(core::f32::consts::FRAC_PI_2),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+
+                let reaches_threshold =
+                    force.is_none() || state.pressure >= self.touch_pressure_threshold;
+
+                // A move for an untracked touch means that its start was dropped,
+                // so it is started here instead.
+                let phase = match phase {
+                    Moved if !self.touches.iter().any(|touch| touch.id == *id) => Started,
+                    phase => *phase,
+                };
+                // The end of an untracked touch is a duplicate, or follows a cancellation,
+                // so it is dropped rather than releasing the touch again.
+                if matches!(phase, Ended | Cancelled)
+                    && !self.touches.iter().any(|touch| touch.id == *id)
+                {
+                    return None;
+                }
+
+                Some(WindowEventTranslation::Pointer(self.counter.attach_count(
+                    match phase {
+                        Started => {
+                            self.touches.retain(|touch| touch.id != *id);
+                            self.touches.push(TouchState {
+                                id: *id,
+                                position: location,
+                                contact: reaches_threshold,
+                                down_position: reaches_threshold.then_some(location),
+                                down_time: reaches_threshold.then_some(time),
+                                move_time: None,
+                                secondary_id,
+                                pointer_type: pointer.pointer_type,
+                                primary,
+                            });
+                            if reaches_threshold {
+                                PointerEvent::Down(PointerButtonEvent {
+                                    pointer,
+                                    button: None,
+                                    state,
+                                })
+                            } else if pointer.pointer_type == PointerType::Pen {
+                                // A pen which is not in contact has come into proximity.
+                                state.pressure = 0.0;
+                                PointerEvent::Enter(pointer, state)
+                            } else {
+                                state.pressure = 0.0;
+                                PointerEvent::Move(PointerUpdate {
+                                    pointer,
+                                    current: state,
+                                    previous_position: None,
+                                    down_position: None,
+                                    velocity: Some(PhysicalPosition::new(0.0, 0.0)),
+                                    coalesced: vec![],
+                                    predicted: vec![],
+                                })
+                            }
+                        }
+                        Moved => {
+                            let touch = self.touches.iter_mut().find(|touch| touch.id == *id);
+                            let previous_position = touch.as_ref().map(|touch| touch.position);
+                            let down_position =
+                                touch.as_ref().and_then(|touch| touch.down_position);
+                            match touch {
+                                Some(touch) if !touch.contact && reaches_threshold => {
+                                    touch.position = location;
+                                    touch.contact = true;
+                                    touch.down_position = Some(location);
+                                    touch.down_time = Some(time);
+                                    PointerEvent::Down(PointerButtonEvent {
+                                        pointer,
+                                        button: None,
+                                        state,
+                                    })
+                                }
+                                _ => {
+                                    let mut velocity = PhysicalPosition::new(0.0, 0.0);
+                                    if let Some(touch) = touch {
+                                        if let Some(last) = touch.move_time {
+                                            if time > last {
+                                                let dt = (time - last) as f64 / 1e9;
+                                                velocity.x = (location.x - touch.position.x) / dt;
+                                                velocity.y = (location.y - touch.position.y) / dt;
+                                            }
+                                        }
+                                        touch.move_time = Some(time);
+                                        touch.position = location;
+                                        if !touch.contact {
+                                            state.pressure = 0.0;
+                                        }
+                                    }
+                                    PointerEvent::Move(PointerUpdate {
+                                        pointer,
+                                        current: state,
+                                        previous_position,
+                                        down_position,
+                                        velocity: Some(velocity),
+                                        coalesced: vec![],
+                                        predicted: vec![],
+                                    })
+                                }
+                            }
+                        }
+                        Cancelled => {
+                            self.touches.retain(|touch| touch.id != *id);
+                            PointerEvent::Cancel(pointer)
+                        }
+                        Ended => {
+                            let touch = self.touches.iter().find(|touch| touch.id == *id);
+                            let contact = touch.map_or(true, |touch| touch.contact);
+                            self.touches.retain(|touch| touch.id != *id);
+                            if contact {
+                                PointerEvent::Up(PointerButtonEvent {
+                                    pointer,
+                                    button: None,
+                                    state,
+                                })
+                            } else {
+                                // The touch never reached the threshold, so it was only hovering.
+                                state.pressure = 0.0;
+                                PointerEvent::Leave(pointer, state)
+                            }
+                        }
+                    },
+                )))
+            }
+            #[cfg(feature = "std")]
+            WindowEvent::HoveredFile(path) if self.file_drops => {
+                Some(WindowEventTranslation::FileDrop(FileDropEvent::Hovered {
+                    path: path.clone(),
+                    position: self.primary_position,
+                }))
+            }
+            #[cfg(feature = "std")]
+            WindowEvent::DroppedFile(path) if self.file_drops => {
+                Some(WindowEventTranslation::FileDrop(FileDropEvent::Dropped {
+                    path: path.clone(),
+                    position: self.primary_position,
+                }))
+            }
+            #[cfg(feature = "std")]
+            WindowEvent::HoveredFileCancelled if self.file_drops => {
+                Some(WindowEventTranslation::FileDrop(FileDropEvent::Cancelled))
+            }
+            WindowEvent::PinchGesture { delta, phase, .. } => {
+                self.accumulate_gesture(*phase, |g| &mut g.pinch, |g| g.scale *= 1.0 + delta);
+                Some(WindowEventTranslation::Gesture(GestureEvent::Pinch(
+                    PinchEvent {
+                        pointer: mouse,
+                        state: self.primary_state.clone(),
+                        delta: *delta,
+                        phase: gesture_phase(*phase),
+                    },
+                )))
+            }
+            WindowEvent::RotationGesture { delta, phase, .. } => {
+                self.accumulate_gesture(*phase, |g| &mut g.rotation, |g| g.rotation += delta);
+                None
+            }
+            WindowEvent::PanGesture { delta, phase, .. } => {
+                self.accumulate_gesture(
+                    *phase,
+                    |g| &mut g.pan,
+                    |g| {
+                        g.pan.x += delta.x;
+                        g.pan.y += delta.y;
+                    },
+                );
+                None
+            }
+            WindowEvent::DoubleTapGesture { .. } => Some(WindowEventTranslation::Gesture(
+                GestureEvent::SmartZoom(SmartZoomEvent {
+                    pointer: mouse,
+                    state: self.primary_state.clone(),
+                }),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Pointer info of the primary mouse pointer.
+const PRIMARY_MOUSE: PointerInfo = PointerInfo {
+    pointer_id: Some(PointerId::PRIMARY),
+    // TODO: Maybe transmute device.
+    persistent_device_id: None,
+    pointer_type: PointerType::Mouse,
+};
+
+/// Result of [`WindowEventReducer::reduce`].
+#[derive(Clone, Debug)]
+pub enum WindowEventTranslation {
+    /// Resulting [`KeyboardEvent`].
+    Keyboard(KeyboardEvent),
+    /// Resulting [`PointerEvent`].
+    Pointer(PointerEvent),
+    /// Resulting [`GestureEvent`].
+    Gesture(GestureEvent),
+    /// Resulting [`FileDropEvent`].
+    ///
+    /// This needs the `std` feature.
+    #[cfg(feature = "std")]
+    FileDrop(FileDropEvent),
+}
+
+/// A file being dragged over or dropped on the window.
+///
+/// [`winit`] reports each file separately, so dragging several files produces
+/// one event for each of them. These are only translated once
+/// [enabled](WindowEventReducer::set_file_drops).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileDropEvent {
+    /// A file is being dragged over the window.
+    Hovered {
+        /// Path of the file.
+        path: PathBuf,
+        /// Last known position of the cursor, if any.
+        ///
+        /// Platforms may not report cursor moves during a drag, so this can be stale.
+        position: Option<PhysicalPosition<f64>>,
+    },
+    /// A file was dropped on the window.
+    Dropped {
+        /// Path of the file.
+        path: PathBuf,
+        /// Last known position of the cursor, if any.
+        ///
+        /// Platforms may not report cursor moves during a drag, so this can be stale.
+        position: Option<PhysicalPosition<f64>>,
+    },
+    /// The files being dragged left the window without being dropped.
+    Cancelled,
+}
+
+impl From<KeyboardEvent> for WindowEventTranslation {
+    fn from(e: KeyboardEvent) -> Self {
+        Self::Keyboard(e)
+    }
+}
+
+impl From<PointerEvent> for WindowEventTranslation {
+    fn from(e: PointerEvent) -> Self {
+        Self::Pointer(e)
+    }
+}
+
+impl From<GestureEvent> for WindowEventTranslation {
+    fn from(e: GestureEvent) -> Self {
+        Self::Gesture(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FileDropEvent> for WindowEventTranslation {
+    fn from(e: FileDropEvent) -> Self {
+        Self::FileDrop(e)
+    }
+}
+
+/// Returns the translation unchanged if it isn't a [`KeyboardEvent`].
+impl TryFrom<WindowEventTranslation> for KeyboardEvent {
+    type Error = WindowEventTranslation;
+
+    fn try_from(t: WindowEventTranslation) -> Result<Self, Self::Error> {
+        match t {
+            WindowEventTranslation::Keyboard(e) => Ok(e),
+            t => Err(t),
+        }
+    }
+}
+
+/// Returns the translation unchanged if it isn't a [`PointerEvent`].
+impl TryFrom<WindowEventTranslation> for PointerEvent {
+    type Error = WindowEventTranslation;
+
+    fn try_from(t: WindowEventTranslation) -> Result<Self, Self::Error> {
+        match t {
+            WindowEventTranslation::Pointer(e) => Ok(e),
+            t => Err(t),
+        }
+    }
+}
+
+/// Returns the translation unchanged if it isn't a [`GestureEvent`].
+impl TryFrom<WindowEventTranslation> for GestureEvent {
+    type Error = WindowEventTranslation;
+
+    fn try_from(t: WindowEventTranslation) -> Result<Self, Self::Error> {
+        match t {
+            WindowEventTranslation::Gesture(e) => Ok(e),
+            t => Err(t),
+        }
+    }
+}
+
+/// Returns the translation unchanged if it isn't a [`FileDropEvent`].
+#[cfg(feature = "std")]
+impl TryFrom<WindowEventTranslation> for FileDropEvent {
+    type Error = WindowEventTranslation;
+
+    fn try_from(t: WindowEventTranslation) -> Result<Self, Self::Error> {
+        match t {
+            WindowEventTranslation::FileDrop(e) => Ok(e),
+            t => Err(t),
+        }
+    }
+}
+
+/// A problem with a [`WindowEvent`], reported by [`WindowEventReducer::try_reduce`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReduceError {
+    /// A cursor or touch position is not finite.
+    NonFinitePosition,
+    /// A mouse button has no [`PointerButton`] representation.
+    UnmappedButton(MouseButton),
+    /// Neither the logical nor the physical key of a key event is identified.
+    UnidentifiedKey,
+}
+
+impl fmt::Display for ReduceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinitePosition => write!(f, "position is not finite"),
+            Self::UnmappedButton(button) => write!(f, "mouse button {button:?} is not mapped"),
+            Self::UnidentifiedKey => write!(f, "key is not identified"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReduceError {}
+
+/// Accumulated state of a touchpad gesture, see [`WindowEventReducer::current_gesture`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureState {
+    /// Scale since the gesture began, where `1.0` is the original size.
+    pub scale: f64,
+    /// Rotation since the gesture began, in degrees, where positive is counterclockwise.
+    pub rotation: f32,
+    /// Distance panned since the gesture began, in physical pixels.
+    ///
+    /// This is not affected by the coordinate transform.
+    pub pan: PhysicalPosition<f32>,
+}
+
+/// A touchpad gesture in progress.
+#[derive(Clone, Copy, Debug)]
+struct ActiveGesture {
+    /// The accumulated state.
+    state: GestureState,
+    /// Whether a pinch is in progress.
+    pinch: bool,
+    /// Whether a rotation is in progress.
+    rotation: bool,
+    /// Whether a pan is in progress.
+    pan: bool,
+}
+
+/// Scroll configuration and state.
+#[derive(Debug)]
+struct ScrollState {
+    /// Multiplier applied to scroll deltas.
+    sensitivity: f64,
+    /// Multiplier applied to the horizontal axis of line deltas.
+    tilt_sensitivity: f64,
+    /// Fractional wheel notches not yet reported.
+    notch_remainder: (f32, f32),
+}
+
+impl Default for ScrollState {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            tilt_sensitivity: 1.0,
+            notch_remainder: (0.0, 0.0),
+        }
+    }
+}
+
+impl ScrollState {
+    /// Apply the sensitivity to a [`ScrollDelta`].
+    #[allow(clippy::cast_possible_truncation)]
+    fn scale(&self, delta: ScrollDelta) -> ScrollDelta {
+        let s = self.sensitivity;
+        match delta {
+            ScrollDelta::PageDelta(x, y) => {
+                ScrollDelta::PageDelta((f64::from(x) * s) as f32, (f64::from(y) * s) as f32)
+            }
+            ScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(
+                (f64::from(x) * s * self.tilt_sensitivity) as f32,
+                (f64::from(y) * s) as f32,
+            ),
+            ScrollDelta::PixelDelta(p) => {
+                ScrollDelta::PixelDelta(PhysicalPosition::new(p.x * s, p.y * s))
+            }
+        }
+    }
+}
+
+/// Pressure of a touch in contact without force information, unless configured.
+const DEFAULT_TOUCH_PRESSURE: f32 = 0.5;
+
+/// The [`GesturePhase`] of a touchpad gesture in `phase`.
+fn gesture_phase(phase: TouchPhase) -> GesturePhase {
+    match phase {
+        TouchPhase::Started => GesturePhase::Started,
+        TouchPhase::Moved => GesturePhase::Changed,
+        TouchPhase::Ended => GesturePhase::Ended,
+        TouchPhase::Cancelled => GesturePhase::Cancelled,
+    }
+}
+
+/// Normalized pressure of a [`Force::Calibrated`].
+///
+/// The force of a stylus is measured along its axis, so the force perpendicular
+/// to the surface is `force * sin(altitude_angle)`. The pressure is then
+/// `0.5 * perpendicular_force`, so that the force of an average touch corresponds
+/// to the pressure of a contact without force information, clamped to `0.0..=1.0`.
+#[allow(clippy::cast_possible_truncation)]
+fn calibrated_pressure(force: f64, altitude_angle: Option<f64>) -> f32 {
+    let perpendicular = match altitude_angle {
+        Some(altitude) => force * altitude.sin(),
+        None => force,
+    };
+    (perpendicular * 0.5).clamp(0.0, 1.0) as f32
+}
+
+/// Returns `true` if both coordinates of `position` are finite.
+fn is_finite(position: PhysicalPosition<f64>) -> bool {
+    position.x.is_finite() && position.y.is_finite()
+}
+
+/// Offset of the [`PointerId`]s of touches from their platform IDs.
+///
+/// This skips [`PointerId::PRIMARY`] and [`SECONDARY_MOUSE_ID`], so touches
+/// only have a reserved ID when they are primary.
+const TOUCH_ID_OFFSET: u64 = 3;
+
+/// [`PointerId`] of the mouse while a touch is primary.
+const SECONDARY_MOUSE_ID: u64 = 2;
+
+/// Largest [`PointerId`] which leaves room for a generation, see [`PointerId::with_generation`].
+const MAX_GENERATION_TOUCH_ID: u64 = (1 << 48) - 1;
+
+/// Tracking state for an active touch.
+#[derive(Clone, Debug)]
+struct TouchState {
+    /// Touch ID from [`Touch::id`].
+    id: u64,
+    /// Last known position.
+    position: PhysicalPosition<f64>,
+    /// Whether the touch has reached the pressure threshold for contact.
+    contact: bool,
+    /// Position where the touch reached contact.
+    down_position: Option<PhysicalPosition<f64>>,
+    /// Nanosecond timestamp when the touch reached contact.
+    down_time: Option<u64>,
+    /// Nanosecond timestamp of the last move of the touch, if it moved since it started.
+    move_time: Option<u64>,
+    /// [`PointerId`] of the touch when it isn't primary.
+    secondary_id: Option<PointerId>,
+    /// Type of the pointer, which is a pen for styluses.
+    pointer_type: PointerType,
+    /// Whether the touch has [`PointerId::PRIMARY`].
+    primary: bool,
+}
+
+impl TouchState {
+    /// The [`PointerId`] of this touch.
+    fn pointer_id(&self) -> Option<PointerId> {
+        if self.primary {
+            Some(PointerId::PRIMARY)
+        } else {
+            self.secondary_id
+        }
+    }
+
+    /// The [`PointerInfo`] of this touch.
+    fn pointer_info(&self) -> PointerInfo {
+        PointerInfo {
+            pointer_id: self.pointer_id(),
+            persistent_device_id: None,
+            pointer_type: self.pointer_type,
+        }
+    }
+}
+
+/// Configuration of palm rejection for touches.
+///
+/// Touches which start close to the window edges and end quickly are usually
+/// accidental contacts from the palm or the hand holding the device.
+/// Such touches end with [`PointerEvent::Cancel`] instead of [`PointerEvent::Up`].
+///
+/// The contact size of touches is not reported by [`winit`], so it is not considered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PalmRejection {
+    /// Width of the margin along the window edges, in physical pixels.
+    pub edge_margin: f64,
+    /// Touches starting in the margin which end sooner than this are rejected, in nanoseconds.
+    pub min_duration: u64,
+}
+
+impl Default for PalmRejection {
+    fn default() -> Self {
+        Self {
+            edge_margin: 16.0,
+            min_duration: 100_000_000,
+        }
+    }
+}
+
+/// Thresholds for a click or tap to continue a click or tap sequence.
+///
+/// A press continues the sequence of an earlier one when it is closer than
+/// [`max_distance`](Self::max_distance) to it, and sooner than
+/// [`max_interval`](Self::max_interval) after its release. High density touch
+/// screens may need a larger distance, and some users need a longer interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TapConfig {
+    /// Maximum distance between presses, in physical pixels after the coordinate transform.
+    pub max_distance: f64,
+    /// Maximum time from a release to the next press, in nanoseconds.
+    pub max_interval: u64,
+}
+
+impl Default for TapConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: 4.0,
+            max_interval: 500_000_000,
+        }
+    }
+}
+
+/// Which pointer has [`PointerId::PRIMARY`] on devices with both a mouse and touch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrimaryPointerPolicy {
+    /// The mouse is always primary, and touches never are.
+    #[default]
+    MousePreferred,
+    /// Whichever pointer becomes active first is primary.
+    ///
+    /// A touch which starts while no mouse button is held and no other touch is primary
+    /// is primary until it ends. Meanwhile, the mouse has another [`PointerId`].
+    FirstContact,
+}
+
+/// Handling of event timestamps which are earlier than the previous one.
+///
+/// This can happen when the clock is adjusted, or when streams of events are merged.
+/// Click and tap counting compares the times of events, so it would misbehave.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimestampPolicy {
+    /// Use the time of the previous event instead, so time never goes backwards.
+    #[default]
+    Clamp,
+    /// Use the new time, and forget click and tap sequences in progress.
+    ResetTaps,
+}
+
+/// Configuration of the jitter filter for pointer positions.
+///
+/// Moves shorter than the [`amplitude`](Self::amplitude) are usually tremors,
+/// so they are smoothed by only moving part of the way towards the new position.
+/// Longer moves are delivered unchanged, so that deliberate movement doesn't lag.
+/// This can help users with motor impairments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JitterFilter {
+    /// Moves shorter than this are smoothed, in physical pixels.
+    pub amplitude: f64,
+    /// Fraction of a smoothed move which is applied, from `0.0` to `1.0`.
+    ///
+    /// At `0.0` small moves are ignored entirely, and at `1.0` they are not smoothed.
+    pub smoothing: f64,
+}
+
+impl Default for JitterFilter {
+    fn default() -> Self {
+        Self {
+            amplitude: 3.0,
+            smoothing: 0.25,
+        }
+    }
+}
+
+/// Recent states of a pointer.
+#[derive(Clone, Debug)]
+struct PointerStates {
+    /// Pointer ID the states belong to.
+    pointer_id: PointerId,
+    /// States, oldest first.
+    states: VecDeque<PointerState>,
+    /// Whether the pointer was a touch which ended.
+    ended: bool,
+}
+
+/// Bounded history of pointer states.
+#[derive(Debug)]
+struct PointerHistory {
+    /// Maximum number of states per pointer.
+    capacity: usize,
+    pointers: Vec<PointerStates>,
+}
+
+impl Default for PointerHistory {
+    fn default() -> Self {
+        Self {
+            capacity: 8,
+            pointers: Vec::new(),
+        }
+    }
+}
+
+impl PointerHistory {
+    /// Record the states of a [`PointerEvent`].
+    fn record(&mut self, e: &PointerEvent) {
+        let (pointer, states) = match e {
+            PointerEvent::Down(PointerButtonEvent { pointer, state, .. })
+            | PointerEvent::Up(PointerButtonEvent { pointer, state, .. })
+            | PointerEvent::Scroll(PointerScrollEvent { pointer, state, .. }) => {
+                (pointer, core::slice::from_ref(state))
+            }
+            PointerEvent::Move(update) => {
+                for state in &update.coalesced {
+                    self.push(&update.pointer, state);
+                }
+                (&update.pointer, core::slice::from_ref(&update.current))
+            }
+            PointerEvent::Cancel(pointer) | PointerEvent::Leave(pointer, _) => {
+                self.pointers
+                    .retain(|history| Some(history.pointer_id) != pointer.pointer_id);
+                return;
+            }
+            PointerEvent::Enter(..) => return,
+        };
+        if matches!(e, PointerEvent::Down(..)) && pointer.pointer_type == PointerType::Touch {
+            self.pointers.retain(|history| !history.ended);
+        }
+        for state in states {
+            self.push(pointer, state);
+        }
+        if let PointerEvent::Up(..) = e {
+            if pointer.pointer_type == PointerType::Touch {
+                if let Some(history) = self
+                    .pointers
+                    .iter_mut()
+                    .find(|history| Some(history.pointer_id) == pointer.pointer_id)
+                {
+                    history.ended = true;
+                }
+            }
+        }
+    }
+
+    /// Push a single state for `pointer`.
+    fn push(&mut self, pointer: &PointerInfo, state: &PointerState) {
+        let Some(pointer_id) = pointer.pointer_id else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        let history = match self
+            .pointers
+            .iter()
+            .position(|history| history.pointer_id == pointer_id)
+        {
+            Some(i) => &mut self.pointers[i],
+            None => {
+                self.pointers.push(PointerStates {
+                    pointer_id,
+                    states: VecDeque::with_capacity(self.capacity),
+                    ended: false,
+                });
+                self.pointers.last_mut().unwrap()
+            }
+        };
+        if history.states.len() == self.capacity {
+            history.states.pop_front();
+        }
+        history.states.push_back(state.clone());
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TapState {
+    /// Pointer ID used to attach tap counts to [`PointerEvent::Move`].
+    pointer_id: Option<PointerId>,
+    /// Nanosecond timestamp when the tap went Down.
+    down_time: u64,
+    /// Nanosecond timestamp when the tap went Up.
+    ///
+    /// Resets to `down_time` when tap goes Down.
+    up_time: u64,
+    /// The local tap count as of the last Down phase.
+    count: u8,
+    /// x coordinate.
+    x: f64,
+    /// y coordinate.
+    y: f64,
+}
+
+#[derive(Debug, Default)]
+struct TapCounter {
+    taps: Vec<TapState>,
+    /// Thresholds for continuing a sequence.
+    config: TapConfig,
+    /// Count after which counts wrap back to 1, if any.
+    max_count: Option<u8>,
+    /// Whether counting is disabled, leaving every count at 0.
+    disabled: bool,
+}
+
+impl TapCounter {
+    /// Enhance a [`PointerEvent`] with a `count`.
+    fn attach_count(&mut self, e: PointerEvent) -> PointerEvent {
+        if self.disabled {
+            return e;
+        }
+        match e {
+            PointerEvent::Down(mut event) => {
+                let pointer_id = event.pointer.pointer_id;
+                let position = event.state.position;
+                let time = event.state.time;
+                let TapConfig {
+                    max_distance,
+                    max_interval,
+                } = self.config;
+
+                if let Some(tap) =
+                    self.taps.iter_mut().find(|TapState { x, y, up_time, .. }| {
+                        let dx = (x - position.x).abs();
+                        let dy = (y - position.y).abs();
+                        (dx * dx + dy * dy).sqrt() < max_distance && (up_time + max_interval) > time
+                    })
+                {
+                    let count = match self.max_count {
+                        Some(max) if max > 0 => tap.count % max + 1,
+                        _ => tap.count.saturating_add(1),
+                    };
+                    event.state.count = count;
+                    tap.count = count;
+                    tap.pointer_id = pointer_id;
+                    tap.down_time = time;
+                    tap.up_time = time;
+                    tap.x = position.x;
+                    tap.y = position.y;
+                } else {
+                    let s = TapState {
+                        pointer_id,
+                        down_time: time,
+                        up_time: time,
+                        count: 1,
+                        x: position.x,
+                        y: position.y,
+                    };
+                    self.taps.push(s);
+                    event.state.count = 1;
+                };
+                self.clear_expired(time);
+                PointerEvent::Down(event)
+            }
+            PointerEvent::Up(mut event) => {
+                let p_id = event.pointer.pointer_id;
+                if let Some(tap) = self.taps.iter_mut().find(|state| state.pointer_id == p_id) {
+                    tap.up_time = event.state.time;
+                    event.state.count = tap.count;
+                }
+                PointerEvent::Up(event)
+            }
+            PointerEvent::Move(mut update) => {
+                if let Some(TapState { count, .. }) = self
+                    .taps
+                    .iter()
+                    .find(
+                        |TapState {
+                             pointer_id,
+                             down_time,
+                             up_time,
+                             ..
+                         }| {
+                            *pointer_id == update.pointer.pointer_id && down_time == up_time
+                        },
+                    )
+                    .cloned()
+                {
+                    update.current.count = count;
+                    for event in update.coalesced.iter_mut() {
+                        event.count = count;
+                    }
+                    for event in update.predicted.iter_mut() {
+                        event.count = count;
+                    }
+                }
+                PointerEvent::Move(update)
+            }
+            PointerEvent::Cancel(p) => {
+                self.taps
+                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
+                PointerEvent::Cancel(p)
+            }
+            PointerEvent::Leave(p, state) => {
+                self.taps
+                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
+                PointerEvent::Leave(p, state)
+            }
+            e @ (PointerEvent::Enter(..) | PointerEvent::Scroll(..)) => e,
+        }
+    }
+
+    /// The count of the sequence of `pointer_id`, if a click at `now` could continue it.
+    fn is_sequence_live(&self, pointer_id: PointerId, now: u64) -> Option<u8> {
+        self.taps
+            .iter()
+            .find(|tap| tap.pointer_id == Some(pointer_id))
+            .filter(|tap| {
+                tap.down_time == tap.up_time || (tap.up_time + self.config.max_interval) > now
+            })
+            .map(|tap| tap.count)
+    }
+
+    /// Clear expired taps.
+    ///
+    /// `t` is the time of the last received event.
+    /// All events have the same time base on Android, so this is valid here.
+    fn clear_expired(&mut self, t: u64) {
+        let max_interval = self.config.max_interval;
+        self.taps.retain(
+            |TapState {
+                 down_time, up_time, ..
+             }| { down_time == up_time || (up_time + max_interval) > t },
+        );
+    }
+}
+
+// The tests use default reducers, whose clock needs `std`.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use ui_events::keyboard::{Code, KeyState};
+    use ui_events::pointer::{PointerButton, PointerButtons};
+    use winit::dpi::LogicalPosition;
+    use winit::event::DeviceId;
+
+    /// A converted key event for [`WindowEventReducer::translate_key`], as `winit`
+    /// key events can't be constructed.
+    fn key_event(code: Code, key: &str, state: KeyState) -> KeyboardEvent {
+        KeyboardEvent {
+            key: Key::Character(key.into()),
+            code,
+            state,
+            ..KeyboardEvent::default()
+        }
+    }
+
+    fn pointer_update(t: Option<WindowEventTranslation>) -> PointerUpdate {
+        match t {
+            Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) => update,
+            other => panic!("expected a pointer move, got {other:?}"),
+        }
+    }
+
+    /// The first move after entering has no delta, later moves are relative to the previous one.
+    #[test]
+    fn move_delta() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_entered());
+
+        // The first move is preceded by the enter.
+        let first = pointer_update(reducer.reduce_all(&cursor_moved(10.0, 20.0)).pop());
+        assert_eq!(first.previous_position, None, "no previous position yet");
+        assert_eq!(first.delta(), PhysicalPosition::new(0.0, 0.0), "zero delta");
+
+        let second = pointer_update(reducer.reduce(&cursor_moved(13.0, 16.0)));
+        assert_eq!(
+            second.delta(),
+            PhysicalPosition::new(3.0, -4.0),
+            "delta from previous move"
+        );
+
+        reducer.reduce(&cursor_left());
+        let after_leave = pointer_update(reducer.reduce(&cursor_moved(50.0, 50.0)));
+        assert_eq!(
+            after_leave.delta(),
+            PhysicalPosition::new(0.0, 0.0),
+            "delta resets after leaving"
+        );
+    }
+
+    /// Deltas across a scale factor change are physical, so they convert to the
+    /// logical delta between the positions at the new scale factor.
+    #[test]
+    fn delta_across_scale_change() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_scale_factor(1.0);
+        reducer.reduce_all(&cursor_moved(10.0, 20.0));
+        // `ScaleFactorChanged` can't be constructed, see `set_scale_factor`.
+        reducer.set_scale_factor(2.0);
+        let update = pointer_update(reducer.reduce(&cursor_moved(14.0, 28.0)));
+        assert_eq!(
+            update.delta(),
+            PhysicalPosition::new(4.0, 8.0),
+            "the delta is between physical positions"
+        );
+        let scale_factor = reducer.scale_factor().unwrap();
+        let logical: LogicalPosition<f64> = update.delta().to_logical(scale_factor);
+        assert_eq!(
+            logical,
+            LogicalPosition::new(2.0, 4.0),
+            "the logical delta uses the new scale factor for both positions"
+        );
+    }
+
+    /// The enter of the cursor is delivered with its last known position, or its
+    /// first position if none is known yet, and `reduce` doesn't lose moves.
+    #[test]
+    fn enter_position() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_entered());
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Enter(_, state))) =
+            reducer.reduce(&cursor_moved(5.0, 6.0))
+        else {
+            panic!("expected the deferred enter from reduce");
+        };
+        assert_eq!(
+            state.position,
+            PhysicalPosition::new(5.0, 6.0),
+            "it stands in for the first move, with its position"
+        );
+
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer.reduce(&cursor_entered()).is_none(),
+            "the enter waits for a position"
+        );
+        let mut moved = reducer.reduce_all(&cursor_moved(5.0, 6.0)).into_iter();
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Enter(pointer, state))) =
+            moved.next()
+        else {
+            panic!("expected an enter");
+        };
+        let update = pointer_update(moved.next());
+        assert_eq!(pointer, update.pointer, "the enter is of the mouse");
+        assert_eq!(
+            state.position,
+            PhysicalPosition::new(5.0, 6.0),
+            "the enter has the position of the first move"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&cursor_left()),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Leave(..)))
+            ),
+            "the cursor leaves"
+        );
+
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Enter(_, state))) =
+            reducer.reduce(&cursor_entered())
+        else {
+            panic!("expected an enter once a position is known");
+        };
+        assert_eq!(
+            state.position,
+            PhysicalPosition::new(5.0, 6.0),
+            "the enter has the last known position"
+        );
+        let update = pointer_update(reducer.reduce(&cursor_moved(9.0, 9.0)));
+        assert_eq!(
+            update.current.position,
+            PhysicalPosition::new(9.0, 9.0),
+            "reduce returns the first move of the hover"
+        );
+        assert_eq!(update.previous_position, None, "the hover starts afresh");
+        reducer.reduce(&cursor_left());
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_entered());
+        assert!(
+            reducer.reduce(&cursor_left()).is_none(),
+            "a cursor which never moved neither enters nor leaves"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&cursor_moved(1.0, 1.0)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Move(..)))
+            ),
+            "moves without entering are not entered"
+        );
+    }
+
+    /// The cursor leaves with its last position and held buttons, and its taps are forgotten.
+    #[test]
+    fn leave_state() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_entered());
+        reducer.reduce(&cursor_moved(1.0, 1.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        reducer.reduce(&cursor_moved(7.0, 8.0));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Leave(_, state))) =
+            reducer.reduce(&cursor_left())
+        else {
+            panic!("expected a leave");
+        };
+        assert_eq!(
+            state.position,
+            PhysicalPosition::new(7.0, 8.0),
+            "the last position"
+        );
+        assert!(
+            state.buttons.contains(PointerButton::Primary),
+            "the held button"
+        );
+
+        reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+        reducer.reduce(&cursor_entered());
+        reducer.reduce(&cursor_moved(7.0, 8.0));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+        else {
+            panic!("expected a down");
+        };
+        assert_eq!(down.state.count, 1, "leaving ends the click sequence");
+    }
+
+    /// Translations follow the order of the events that produced them, including
+    /// the translations of events which produce several.
+    #[test]
+    fn translation_order() {
+        let mut reducer = WindowEventReducer::default();
+        let events = [
+            cursor_entered(),
+            // The first move after entering is an enter, then a move.
+            cursor_moved(1.0, 1.0),
+            mouse_input(ElementState::Pressed, MouseButton::Left),
+            cursor_moved(2.0, 2.0),
+            mouse_input(ElementState::Released, MouseButton::Left),
+            cursor_left(),
+            touch(TouchPhase::Started, 1, 1.0, 1.0, None),
+            // A touch which starts again is cancelled, then goes down again.
+            touch(TouchPhase::Started, 1, 2.0, 2.0, None),
+            touch(TouchPhase::Ended, 1, 2.0, 2.0, None),
+        ];
+        let kinds: Vec<&str> = events
+            .iter()
+            .flat_map(|e| reducer.reduce_all(e))
+            .map(|t| match t {
+                WindowEventTranslation::Pointer(PointerEvent::Enter(..)) => "enter",
+                WindowEventTranslation::Pointer(PointerEvent::Move(..)) => "move",
+                WindowEventTranslation::Pointer(PointerEvent::Down(..)) => "down",
+                WindowEventTranslation::Pointer(PointerEvent::Up(..)) => "up",
+                WindowEventTranslation::Pointer(PointerEvent::Leave(..)) => "leave",
+                WindowEventTranslation::Pointer(PointerEvent::Cancel(..)) => "cancel",
+                other => panic!("unexpected translation {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            ["enter", "move", "down", "move", "up", "leave", "down", "cancel", "down", "up"],
+            "translations should be in event order"
+        );
+    }
+
+    /// Smart magnification is translated to a smart zoom at the cursor position.
+    #[test]
+    fn smart_zoom() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(5.0, 7.0));
+        let Some(WindowEventTranslation::Gesture(GestureEvent::SmartZoom(event))) =
+            reducer.reduce(&WindowEvent::DoubleTapGesture {
+                device_id: DeviceId::dummy(),
+            })
+        else {
+            panic!("expected a smart zoom");
+        };
+        assert_eq!(
+            event.state.position,
+            PhysicalPosition::new(5.0, 7.0),
+            "smart zoom should be at the cursor position"
+        );
+    }
+
+    /// Touchpad pinches are translated at the cursor, with their deltas and phases.
+    #[test]
+    fn pinch() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(5.0, 7.0));
+        reducer.reduce(&modifiers_changed(ModifiersState::CONTROL));
+        let mut pinch = |delta, phase| match reducer.reduce(&WindowEvent::PinchGesture {
+            device_id: DeviceId::dummy(),
+            delta,
+            phase,
+        }) {
+            Some(WindowEventTranslation::Gesture(GestureEvent::Pinch(event))) => event,
+            other => panic!("expected a pinch, got {other:?}"),
+        };
+
+        let started = pinch(0.25, TouchPhase::Started);
+        assert_eq!(
+            started.state.position,
+            PhysicalPosition::new(5.0, 7.0),
+            "the pinch is at the cursor"
+        );
+        assert_eq!(
+            started.state.modifiers,
+            Modifiers::CONTROL,
+            "the pinch has the modifiers"
+        );
+        let steps = [
+            started,
+            pinch(-0.5, TouchPhase::Moved),
+            pinch(0.0, TouchPhase::Ended),
+        ]
+        .map(|event| (event.delta, event.phase));
+        assert_eq!(
+            steps,
+            [
+                (0.25, GesturePhase::Started),
+                (-0.5, GesturePhase::Changed),
+                (0.0, GesturePhase::Ended)
+            ],
+            "the deltas and phases of the pinch"
+        );
+        assert_eq!(
+            pinch(0.1, TouchPhase::Cancelled).phase,
+            GesturePhase::Cancelled,
+            "a cancelled pinch"
+        );
+    }
+
+    /// The source of a translation is only recorded when enabled.
+    #[test]
+    fn last_source() {
+        let mut reducer = WindowEventReducer::default();
+        let moved = cursor_moved(1.0, 1.0);
+        reducer.reduce(&moved);
+        assert_eq!(reducer.last_source(), None, "recording is off by default");
+
+        reducer.set_record_source(true);
+        reducer.reduce(&moved);
+        assert_eq!(
+            reducer.last_source(),
+            Some(core::mem::discriminant(&moved)),
+            "source should be the cursor move"
+        );
+
+        let pressed = mouse_input(ElementState::Pressed, MouseButton::Left);
+        reducer.reduce(&pressed);
+        assert_eq!(
+            reducer.last_source(),
+            Some(core::mem::discriminant(&pressed)),
+            "source should be the mouse input"
+        );
+
+        // Ignored events don't replace the source.
+        reducer.reduce(&WindowEvent::RedrawRequested);
+        assert_eq!(
+            reducer.last_source(),
+            Some(core::mem::discriminant(&pressed)),
+            "ignored events are not recorded"
+        );
+    }
+
+    /// Touches below the pressure threshold hover until they reach it.
+    #[test]
+    fn touch_pressure_threshold() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_touch_pressure_threshold(0.3);
+
+        let light = Some(Force::Normalized(0.1));
+        let firm = Some(Force::Normalized(0.6));
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Started, 0, 1.0, 1.0, light)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Move(..)))
+            ),
+            "a light touch hovers"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Moved, 0, 1.0, 1.0, light)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Move(..)))
+            ),
+            "a light touch keeps hovering"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Moved, 0, 1.0, 1.0, firm)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(..)))
+            ),
+            "a firm touch goes down"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Ended, 0, 1.0, 1.0, firm)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Up(..)))
+            ),
+            "a touch in contact goes up"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, light));
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Ended, 1, 1.0, 1.0, light)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Leave(..)))
+            ),
+            "a hovering touch leaves"
+        );
+
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Started, 2, 1.0, 1.0, None)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(..)))
+            ),
+            "a touch without force goes down"
+        );
+    }
+
+    /// Touches without force information have the default pressure until they end.
+    #[test]
+    fn touch_pressure_phases() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_default_touch_pressure(0.75);
+        let force = Some(Force::Normalized(0.25));
+        for (force, active) in [(None, 0.75), (force, 0.25)] {
+            let mut pressure = |phase| match reducer.reduce(&touch(phase, 1, 1.0, 1.0, force)) {
+                Some(WindowEventTranslation::Pointer(
+                    PointerEvent::Down(PointerButtonEvent { state, .. })
+                    | PointerEvent::Up(PointerButtonEvent { state, .. }),
+                )) => Some(state.pressure),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) => {
+                    Some(update.current.pressure)
+                }
+                _ => None,
+            };
+            assert_eq!(pressure(TouchPhase::Started), Some(active), "started");
+            assert_eq!(pressure(TouchPhase::Moved), Some(active), "moved");
+            assert_eq!(pressure(TouchPhase::Ended), Some(0.0), "ended");
+            pressure(TouchPhase::Started);
+            assert_eq!(
+                pressure(TouchPhase::Cancelled),
+                None,
+                "a cancel has no state"
+            );
+        }
+    }
+
+    /// An angled stylus applies less pressure than a perpendicular one with the same force.
+    #[test]
+    fn stylus_pressure() {
+        let mut reducer = WindowEventReducer::default();
+        let mut pressure = |id, altitude_angle| {
+            let force = Some(Force::Calibrated {
+                force: 1.0,
+                max_possible_force: 4.0,
+                altitude_angle,
+            });
+            match reducer.reduce(&touch(TouchPhase::Started, id, 1.0, 1.0, force)) {
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) => {
+                    down.state.pressure
+                }
+                other => panic!("expected a pointer down, got {other:?}"),
+            }
+        };
+
+        let finger = pressure(0, None);
+        let perpendicular = pressure(1, Some(core::f64::consts::FRAC_PI_2));
+        let angled = pressure(2, Some(core::f64::consts::FRAC_PI_6));
+        assert_eq!(finger, 0.5, "an average touch has the default pressure");
+        assert!(
+            (perpendicular - finger).abs() < 1e-6,
+            "a perpendicular stylus applies all its force"
+        );
+        assert!(
+            (angled - finger * 0.5).abs() < 1e-6,
+            "an angled stylus applies part of its force"
+        );
+    }
+
+    /// A stylus below the pressure threshold enters, hovers with no pressure, and leaves.
+    #[test]
+    fn stylus_hover() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_touch_pressure_threshold(0.3);
+
+        let pen = |force| {
+            Some(Force::Calibrated {
+                force,
+                max_possible_force: 2.0,
+                altitude_angle: Some(1.0),
+            })
+        };
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Enter(pointer, _))) =
+            reducer.reduce(&touch(TouchPhase::Started, 0, 1.0, 1.0, pen(0.2)))
+        else {
+            panic!("a hovering stylus should enter");
+        };
+        assert_eq!(pointer.pointer_type, PointerType::Pen, "a stylus is a pen");
+
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) =
+            reducer.reduce(&touch(TouchPhase::Moved, 0, 2.0, 1.0, pen(0.2)))
+        else {
+            panic!("a hovering stylus should move");
+        };
+        assert_eq!(
+            update.current.pressure, 0.0,
+            "a hovering stylus has no pressure"
+        );
+        assert_eq!(
+            update.current.orientation.altitude, 1.0,
+            "altitude is reported"
+        );
+
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Moved, 0, 2.0, 1.0, pen(1.0))),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(..)))
+            ),
+            "a stylus in contact goes down"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Ended, 0, 2.0, 1.0, pen(0.0))),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Up(..)))
+            ),
+            "a stylus lifted goes up"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, pen(0.2)));
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Ended, 1, 1.0, 1.0, pen(0.2))),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Leave(..)))
+            ),
+            "a stylus leaving proximity leaves"
+        );
+    }
+
+    /// Buttons pressed together are added and removed in the order they are reported.
+    #[test]
+    fn multi_button_order() {
+        let mut reducer = WindowEventReducer::default();
+        let mut button_event = |we: WindowEvent| match reducer.reduce(&we) {
+            Some(WindowEventTranslation::Pointer(
+                PointerEvent::Down(event) | PointerEvent::Up(event),
+            )) => (event.button, event.state.buttons),
+            other => panic!("expected a button event, got {other:?}"),
+        };
+
+        assert_eq!(
+            button_event(mouse_input(ElementState::Pressed, MouseButton::Left)),
+            (Some(PointerButton::Primary), PointerButton::Primary.into()),
+            "left press"
+        );
+        assert_eq!(
+            button_event(mouse_input(ElementState::Pressed, MouseButton::Right)),
+            (
+                Some(PointerButton::Secondary),
+                PointerButton::Primary | PointerButton::Secondary
+            ),
+            "right press while left is held"
+        );
+        assert_eq!(
+            button_event(mouse_input(ElementState::Released, MouseButton::Left)),
+            (
+                Some(PointerButton::Primary),
+                PointerButton::Secondary.into()
+            ),
+            "left release while right is held"
+        );
+        assert_eq!(
+            button_event(mouse_input(ElementState::Released, MouseButton::Right)),
+            (Some(PointerButton::Secondary), PointerButtons::new()),
+            "right release"
+        );
+    }
+
+    /// Positions are transformed before they are stored and counted.
+    #[test]
+    fn coordinate_transform() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_coordinate_transform(Affine::scale(2.0).then_translate((1.0, 0.0).into()));
+
+        let update = pointer_update(reducer.reduce(&cursor_moved(3.0, 4.0)));
+        assert_eq!(
+            update.current.position,
+            PhysicalPosition::new(7.0, 8.0),
+            "cursor position should be transformed"
+        );
+
+        // 1.5 apart before the transform, but 3.0 apart after it, which is still within tap distance:
+        // the second tap continues the sequence.
+        reducer.reduce(&touch(TouchPhase::Started, 0, 0.0, 0.0, None));
+        reducer.reduce(&touch(TouchPhase::Ended, 0, 0.0, 0.0, None));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&touch(TouchPhase::Started, 1, 1.5, 0.0, None))
+        else {
+            panic!("expected a touch down");
+        };
+        assert_eq!(
+            down.state.position,
+            PhysicalPosition::new(4.0, 0.0),
+            "touch position should be transformed"
+        );
+        assert_eq!(down.state.count, 2, "tap distance is in transformed space");
+
+        // 2.5 apart before the transform, but 5.0 apart after it.
+        reducer.reduce(&touch(TouchPhase::Ended, 1, 1.5, 0.0, None));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&touch(TouchPhase::Started, 2, 4.0, 0.0, None))
+        else {
+            panic!("expected a touch down");
+        };
+        assert_eq!(down.state.count, 1, "tap distance is in transformed space");
+    }
+
+    /// A second click at the same place continues the sequence started by the first.
+    #[test]
+    fn click_sequence() {
+        let mut reducer = WindowEventReducer::default();
+        let mut press = || {
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+                reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+            else {
+                panic!("expected a pointer down");
+            };
+            reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+            down
+        };
+
+        let first = press();
+        assert!(first.starts_sequence(), "first click starts a sequence");
+        assert!(!first.continues_sequence(), "first click continues nothing");
+        let second = press();
+        assert!(
+            !second.starts_sequence(),
+            "second click doesn't start a sequence"
+        );
+        assert!(
+            second.continues_sequence(),
+            "second click continues the sequence"
+        );
+    }
+
+    /// File drag and drop events are translated once enabled.
+    #[test]
+    fn file_drops() {
+        let path = PathBuf::from("image.png");
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer
+                .reduce(&WindowEvent::DroppedFile(path.clone()))
+                .is_none(),
+            "file drops are disabled by default"
+        );
+
+        reducer.set_file_drops(true);
+        reducer.reduce(&cursor_moved(3.0, 4.0));
+        let drop = |reducer: &mut WindowEventReducer, we| {
+            FileDropEvent::try_from(reducer.reduce(&we).expect("a translation"))
+                .expect("a file drop")
+        };
+        assert_eq!(
+            drop(&mut reducer, WindowEvent::HoveredFile(path.clone())),
+            FileDropEvent::Hovered {
+                path: path.clone(),
+                position: Some(PhysicalPosition::new(3.0, 4.0)),
+            },
+            "a hovered file"
+        );
+        assert_eq!(
+            drop(&mut reducer, WindowEvent::HoveredFileCancelled),
+            FileDropEvent::Cancelled,
+            "a cancelled drag"
+        );
+        assert_eq!(
+            drop(&mut reducer, WindowEvent::DroppedFile(path.clone())),
+            FileDropEvent::Dropped {
+                path,
+                position: Some(PhysicalPosition::new(3.0, 4.0)),
+            },
+            "a dropped file"
+        );
+    }
+
+    /// The time since a pointer went down is tracked while it is down.
+    #[test]
+    fn time_since_down() {
+        let clock = clock::ManualClock::new(1);
+        let mut reducer = WindowEventReducer::with_clock(clock.clone());
+        assert_eq!(
+            reducer.time_since_down(PointerId::PRIMARY),
+            None,
+            "the mouse is up"
+        );
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        clock.advance(Duration::from_millis(2));
+        assert_eq!(
+            reducer.time_since_down(PointerId::PRIMARY),
+            Some(Duration::from_millis(2)),
+            "time has passed"
+        );
+        reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+        assert_eq!(
+            reducer.time_since_down(PointerId::PRIMARY),
+            None,
+            "the mouse is released"
+        );
+
+        reducer.set_touch_pressure_threshold(0.5);
+        let light = Some(Force::Normalized(0.1));
+        reducer.reduce(&touch(TouchPhase::Started, 2, 1.0, 1.0, light));
+        let touch_id = PointerId::new(2 + TOUCH_ID_OFFSET).unwrap();
+        assert_eq!(
+            reducer.time_since_down(touch_id),
+            None,
+            "a hovering touch is not down"
+        );
+        let firm = Some(Force::Normalized(0.9));
+        reducer.reduce(&touch(TouchPhase::Moved, 2, 1.0, 1.0, firm));
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(
+            reducer.time_since_down(touch_id),
+            Some(Duration::from_millis(5)),
+            "the touch reached contact"
+        );
+    }
+
+    /// The policy decides whether the mouse or the first touch is primary.
+    #[test]
+    fn primary_pointer_policy() {
+        fn pointer_id(reducer: &mut WindowEventReducer, we: WindowEvent) -> Option<PointerId> {
+            match reducer.reduce(&we) {
+                Some(WindowEventTranslation::Pointer(
+                    PointerEvent::Down(PointerButtonEvent { pointer, .. })
+                    | PointerEvent::Up(PointerButtonEvent { pointer, .. })
+                    | PointerEvent::Move(PointerUpdate { pointer, .. }),
+                )) => pointer.pointer_id,
+                other => panic!("expected a pointer event, got {other:?}"),
+            }
+        }
+        let primary = Some(PointerId::PRIMARY);
+
+        let mut reducer = WindowEventReducer::default();
+        let touch_start = touch(TouchPhase::Started, 0, 1.0, 1.0, None);
+        assert_ne!(
+            pointer_id(&mut reducer, touch_start.clone()),
+            primary,
+            "a touch is never primary"
+        );
+        assert_eq!(
+            pointer_id(&mut reducer, cursor_moved(1.0, 1.0)),
+            primary,
+            "the mouse is primary"
+        );
+        reducer.reduce(&touch(TouchPhase::Ended, 0, 1.0, 1.0, None));
+
+        reducer.set_primary_pointer_policy(PrimaryPointerPolicy::FirstContact);
+        assert_eq!(
+            pointer_id(&mut reducer, touch_start.clone()),
+            primary,
+            "the first touch is primary"
+        );
+        let mouse = pointer_id(&mut reducer, cursor_moved(2.0, 1.0));
+        assert!(
+            mouse.is_some() && mouse != primary,
+            "the mouse isn't primary meanwhile"
+        );
+        assert_eq!(
+            pointer_id(&mut reducer, touch(TouchPhase::Ended, 0, 1.0, 1.0, None)),
+            primary,
+            "the touch stays primary until it ends"
+        );
+        assert_eq!(
+            pointer_id(
+                &mut reducer,
+                mouse_input(ElementState::Pressed, MouseButton::Left)
+            ),
+            primary,
+            "the mouse is primary again"
+        );
+        assert_ne!(
+            pointer_id(&mut reducer, touch_start),
+            primary,
+            "a touch during a mouse press isn't primary"
+        );
+    }
+
+    /// Cancelling everything cancels active pointers and resets the state.
+    #[test]
+    fn cancel_all() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&modifiers_changed(ModifiersState::SHIFT));
+        reducer.reduce(&cursor_moved(1.0, 1.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        reducer.reduce(&touch(TouchPhase::Started, 1, 50.0, 50.0, None));
+
+        let cancels = reducer.cancel_all();
+        assert!(
+            matches!(
+                &cancels[..],
+                [
+                    WindowEventTranslation::Pointer(PointerEvent::Cancel(touch)),
+                    WindowEventTranslation::Pointer(PointerEvent::Cancel(mouse)),
+                ] if touch.pointer_type == PointerType::Touch && mouse.is_primary_pointer()
+            ),
+            "the touch and the mouse are cancelled, got {cancels:?}"
+        );
+        assert_eq!(reducer.active_touch_count(), 0, "no touches are active");
+        assert!(
+            reducer.current_modifiers().is_empty(),
+            "modifiers are reset"
+        );
+        assert!(
+            reducer.primary_pointer_state().buttons.is_empty(),
+            "no buttons are held"
+        );
+        assert!(reducer.cancel_all().is_empty(), "nothing is left to cancel");
+
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+        else {
+            panic!("expected a down");
+        };
+        assert_eq!(down.state.count, 1, "the click sequence is reset");
+    }
+
+    /// Touchpad gestures accumulate until all of their parts end.
+    #[test]
+    fn current_gesture() {
+        let device_id = DeviceId::dummy();
+        let mut reducer = WindowEventReducer::default();
+        let pinch = |delta, phase| WindowEvent::PinchGesture {
+            device_id,
+            delta,
+            phase,
+        };
+        let rotation = |delta, phase| WindowEvent::RotationGesture {
+            device_id,
+            delta,
+            phase,
+        };
+        assert_eq!(reducer.current_gesture(), None, "no gesture");
+        reducer.reduce(&pinch(0.5, TouchPhase::Started));
+        reducer.reduce(&rotation(10.0, TouchPhase::Started));
+        reducer.reduce(&pinch(1.0, TouchPhase::Moved));
+        reducer.reduce(&WindowEvent::PanGesture {
+            device_id,
+            delta: PhysicalPosition::new(2.0, -3.0),
+            phase: TouchPhase::Moved,
+        });
+        assert_eq!(
+            reducer.current_gesture(),
+            Some(GestureState {
+                scale: 3.0,
+                rotation: 10.0,
+                pan: PhysicalPosition::new(2.0, -3.0),
+            }),
+            "the gesture is accumulated"
+        );
+        reducer.reduce(&pinch(0.0, TouchPhase::Ended));
+        reducer.reduce(&WindowEvent::PanGesture {
+            device_id,
+            delta: PhysicalPosition::new(0.0, 0.0),
+            phase: TouchPhase::Ended,
+        });
+        assert!(
+            reducer.current_gesture().is_some(),
+            "the rotation is still in progress"
+        );
+        reducer.reduce(&rotation(0.0, TouchPhase::Ended));
+        assert_eq!(reducer.current_gesture(), None, "the gesture ended");
+    }
+
+    /// The current modifiers are available before any keyboard event.
+    #[test]
+    fn current_modifiers() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(reducer.current_modifiers().is_empty(), "no modifiers");
+        reducer.reduce(&modifiers_changed(
+            ModifiersState::SHIFT | ModifiersState::CONTROL,
+        ));
+        assert_eq!(
+            reducer.current_modifiers(),
+            Modifiers::SHIFT | Modifiers::CONTROL,
+            "modifiers are tracked"
+        );
+        assert_eq!(
+            reducer.winit_modifiers(),
+            ModifiersState::SHIFT | ModifiersState::CONTROL,
+            "the winit modifiers are kept"
+        );
+    }
+
+    /// Touches which reuse a platform ID get a new generation.
+    #[test]
+    fn pointer_id_generations() {
+        fn start(reducer: &mut WindowEventReducer) -> PointerId {
+            let down = reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) = down else {
+                panic!("expected a down");
+            };
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+                reducer.reduce(&touch(TouchPhase::Ended, 1, 1.0, 1.0, None))
+            else {
+                panic!("expected an up");
+            };
+            assert_eq!(
+                up.pointer.pointer_id, down.pointer.pointer_id,
+                "a touch keeps its ID"
+            );
+            down.pointer.pointer_id.unwrap()
+        }
+
+        let mut reducer = WindowEventReducer::default();
+        assert_eq!(start(&mut reducer), start(&mut reducer), "IDs are recycled");
+
+        reducer.set_pointer_id_generations(true);
+        let first = start(&mut reducer);
+        let second = start(&mut reducer);
+        assert_ne!(first, second, "a recycled ID is distinguishable");
+        assert_eq!(
+            second.generation(),
+            first.generation() + 1,
+            "the generation increments"
+        );
+        assert_eq!(
+            first.with_generation(0),
+            PointerId::new(1 + TOUCH_ID_OFFSET),
+            "the platform ID is kept"
+        );
+    }
+
+    /// Platform touch IDs which use the high bits are never truncated into collisions.
+    #[test]
+    fn large_touch_ids() {
+        fn down_id(reducer: &mut WindowEventReducer, id: u64, x: f64) -> PointerId {
+            match reducer.reduce(&touch(TouchPhase::Started, id, x, 1.0, None)) {
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) => {
+                    down.pointer.pointer_id.expect("a touch has an ID")
+                }
+                other => panic!("expected a down, got {other:?}"),
+            }
+        }
+        let first = 1 << 50;
+        let second = (1 << 51) | (1 << 50);
+        // Wraps to the primary ID when masked to 48 bits.
+        let wrapping = (1 << 48) - 2;
+
+        let mut reducer = WindowEventReducer::default();
+        down_id(&mut reducer, 0, 0.0);
+        assert_eq!(
+            down_id(&mut reducer, first, 1.0),
+            PointerId::new(first + TOUCH_ID_OFFSET).unwrap(),
+            "without generations, the full platform ID is kept"
+        );
+        assert_eq!(
+            down_id(&mut reducer, second, 2.0),
+            PointerId::new(second + TOUCH_ID_OFFSET).unwrap(),
+            "IDs differing in the high bits stay distinct"
+        );
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_pointer_id_generations(true);
+        down_id(&mut reducer, 0, 0.0);
+        let ids = [
+            down_id(&mut reducer, first, 1.0),
+            down_id(&mut reducer, second, 2.0),
+            down_id(&mut reducer, wrapping, 3.0),
+        ];
+        for (i, id) in ids.iter().enumerate() {
+            let reserved = [
+                PointerId::PRIMARY,
+                PointerId::new(SECONDARY_MOUSE_ID).unwrap(),
+            ];
+            assert!(
+                !reserved
+                    .iter()
+                    .any(|reserved| id.with_generation(0) == Some(*reserved)),
+                "{id:?} doesn't alias a reserved ID"
+            );
+            assert!(
+                ids[i + 1..]
+                    .iter()
+                    .all(|other| other.with_generation(0) != id.with_generation(0)),
+                "{id:?} doesn't collide"
+            );
+        }
+        reducer.reduce(&touch(TouchPhase::Ended, first, 1.0, 1.0, None));
+        assert_eq!(
+            down_id(&mut reducer, first, 1.0).with_generation(0),
+            ids[0].with_generation(0),
+            "a free slot is reused"
+        );
+    }
+
+    /// Only scroll events are returned, and other kinds of input are ignored.
+    #[test]
+    fn reduce_scroll() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer.reduce_scroll(&cursor_moved(3.0, 4.0)).is_none(),
+            "a move is not a scroll"
+        );
+        assert!(
+            reducer
+                .reduce_scroll(&touch(TouchPhase::Started, 1, 1.0, 1.0, None))
+                .is_none(),
+            "a touch is not a scroll"
+        );
+        assert_eq!(reducer.active_touch_count(), 0, "touches are ignored");
+
+        let scroll = reducer
+            .reduce_scroll(&mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0)))
+            .expect("a scroll");
+        assert_eq!(
+            scroll.state.position,
+            PhysicalPosition::new(3.0, 4.0),
+            "the scroll is at the cursor"
+        );
+        assert_eq!(scroll.notches, Some((0, 1)), "one notch");
+    }
+
+    /// Every translation of an event is returned, in order.
+    #[test]
+    fn reduce_all() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer
+                .reduce_all(&modifiers_changed(ModifiersState::SHIFT))
+                .is_empty(),
+            "a modifier change only updates the state"
+        );
+        // Key events can't be constructed, so deliver a translated key like
+        // `reduce_into` does.
+        let mut keys = Vec::new();
+        let key = reducer.translate_key(key_event(Code::KeyA, "a", KeyState::Down), Some("a"));
+        reducer.deliver(key.into(), &mut keys);
+        assert!(
+            matches!(keys.as_slice(), [WindowEventTranslation::Keyboard(_)]),
+            "a key event is a single translation, got {keys:?}"
+        );
+        let started = reducer.reduce_all(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+        let [WindowEventTranslation::Pointer(PointerEvent::Down(first))] = started.as_slice()
+        else {
+            panic!("a touch goes down, got {started:?}");
+        };
+        let restarted = reducer.reduce_all(&touch(TouchPhase::Started, 1, 2.0, 2.0, None));
+        let [WindowEventTranslation::Pointer(PointerEvent::Cancel(cancelled)), WindowEventTranslation::Pointer(PointerEvent::Down(second))] =
+            restarted.as_slice()
+        else {
+            panic!("a restarted touch is cancelled, then goes down again, got {restarted:?}");
+        };
+        assert_eq!(
+            cancelled.pointer_id, first.pointer.pointer_id,
+            "the cancel is for the stale touch"
+        );
+        assert_eq!(
+            first.state.position,
+            PhysicalPosition::new(1.0, 1.0),
+            "the stale touch went down at its first position"
+        );
+        assert_eq!(
+            second.state.position,
+            PhysicalPosition::new(2.0, 2.0),
+            "the restarted touch goes down at its new position, after the cancel"
+        );
+        let cancelled = reducer.reduce_all(&touch(TouchPhase::Cancelled, 1, 2.0, 2.0, None));
+        assert!(
+            matches!(
+                cancelled.as_slice(),
+                [WindowEventTranslation::Pointer(PointerEvent::Cancel(_))]
+            ),
+            "a cancelled touch is cancelled once, got {cancelled:?}"
+        );
+    }
+
+    /// Caller timestamps are used for the state, and for expiring click sequences.
+    #[test]
+    fn reduce_at() {
+        const MS: u64 = 1_000_000;
+        let mut reducer = WindowEventReducer::default();
+        let mut click = |time| {
+            let press = mouse_input(ElementState::Pressed, MouseButton::Left);
+            let down = match reducer.reduce_at(&press, time) {
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) => down,
+                other => panic!("expected a down, got {other:?}"),
+            };
+            let release = mouse_input(ElementState::Released, MouseButton::Left);
+            reducer.reduce_at(&release, time + 50 * MS);
+            down.state
+        };
+
+        let first = click(1000 * MS);
+        assert_eq!(first.time, 1000 * MS, "the caller's timestamp");
+        assert_eq!(click(1400 * MS).count, 2, "a double click");
+        assert_eq!(
+            click(2000 * MS).count,
+            1,
+            "an expired sequence is not continued"
+        );
+    }
+
+    /// Clicks continue a sequence just inside the time and distance limits.
+    #[test]
+    fn tap_boundaries() {
+        const MS: u64 = 1_000_000;
+        fn click(reducer: &mut WindowEventReducer, x: f64, time: u64) -> u8 {
+            let mut out = Vec::new();
+            reducer.reduce_into_at(&cursor_moved(x, 0.0), time, &mut out);
+            let press = mouse_input(ElementState::Pressed, MouseButton::Left);
+            reducer.reduce_into_at(&press, time, &mut out);
+            let release = mouse_input(ElementState::Released, MouseButton::Left);
+            reducer.reduce_into_at(&release, time, &mut out);
+            out.into_iter()
+                .find_map(|translation| match translation {
+                    WindowEventTranslation::Pointer(PointerEvent::Down(down)) => {
+                        Some(down.state.count)
+                    }
+                    _ => None,
+                })
+                .expect("a down")
+        }
+        let second_with = |config, x, interval| {
+            let mut reducer = WindowEventReducer::with_tap_config(config);
+            click(&mut reducer, 10.0, 1000 * MS);
+            click(&mut reducer, x, 1000 * MS + interval)
+        };
+        let second = |x, interval| second_with(TapConfig::default(), x, interval);
+
+        assert_eq!(second(10.0, 499 * MS), 2, "499 ms continues the sequence");
+        assert_eq!(second(10.0, 501 * MS), 1, "501 ms resets the sequence");
+        assert_eq!(second(13.9, 0), 2, "3.9 px continues the sequence");
+        assert_eq!(second(14.1, 0), 1, "4.1 px resets the sequence");
+
+        let config = TapConfig {
+            max_distance: 10.0,
+            max_interval: 800 * MS,
+        };
+        assert_eq!(
+            second_with(config, 10.0, 799 * MS),
+            2,
+            "799 ms continues a configured sequence"
+        );
+        assert_eq!(
+            second_with(config, 10.0, 801 * MS),
+            1,
+            "801 ms resets a configured sequence"
+        );
+        assert_eq!(
+            second_with(config, 19.9, 0),
+            2,
+            "9.9 px continues a configured sequence"
+        );
+        assert_eq!(
+            second_with(config, 20.1, 0),
+            1,
+            "10.1 px resets a configured sequence"
+        );
+    }
+
+    /// Timestamps which go backwards are handled by the policy.
+    #[test]
+    fn backwards_timestamp() {
+        fn down_at(reducer: &mut WindowEventReducer, time: u64) -> PointerState {
+            let mut out = Vec::new();
+            reducer.reduce_into_at(
+                &mouse_input(ElementState::Pressed, MouseButton::Left),
+                time,
+                &mut out,
+            );
+            reducer.reduce_into_at(
+                &mouse_input(ElementState::Released, MouseButton::Left),
+                time,
+                &mut out,
+            );
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+                out.into_iter().next()
+            else {
+                panic!("expected a down");
+            };
+            down.state
+        }
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(1.0, 1.0));
+        assert_eq!(down_at(&mut reducer, 2_000_000_000).count, 1, "first click");
+        let down = down_at(&mut reducer, 1_000_000_000);
+        assert_eq!(down.time, 2_000_000_000, "the time is clamped");
+        assert_eq!(down.count, 2, "the click continues the sequence");
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_timestamp_policy(TimestampPolicy::ResetTaps);
+        reducer.reduce(&cursor_moved(1.0, 1.0));
+        assert_eq!(down_at(&mut reducer, 2_000_000_000).count, 1, "first click");
+        let down = down_at(&mut reducer, 1_000_000_000);
+        assert_eq!(down.time, 1_000_000_000, "the time is kept");
+        assert_eq!(down.count, 1, "the sequence is reset");
+    }
+
+    /// The first touch is reported as the mouse, and later touches as touches.
+    #[test]
+    fn touch_mouse_emulation() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_touch_mouse_emulation(true);
+        let mut pointer = |phase, id| {
+            let Some(WindowEventTranslation::Pointer(e)) =
+                reducer.reduce(&touch(phase, id, 1.0, 1.0, None))
+            else {
+                panic!("expected a pointer event");
+            };
+            e
+        };
+
+        let PointerEvent::Down(down) = pointer(TouchPhase::Started, 5) else {
+            panic!("expected a down");
+        };
+        assert_eq!(
+            (down.pointer.pointer_id, down.pointer.pointer_type),
+            (Some(PointerId::PRIMARY), PointerType::Mouse),
+            "the first touch is the mouse"
+        );
+        assert_eq!(
+            down.pointer.persistent_device_id,
+            PersistentDeviceId::new(6),
+            "the touch ID is kept"
+        );
+        assert_eq!(
+            down.button,
+            Some(PointerButton::Primary),
+            "a contact is a primary button press"
+        );
+        let PointerEvent::Down(second) = pointer(TouchPhase::Started, 6) else {
+            panic!("expected a down");
+        };
+        assert_eq!(
+            second.pointer.pointer_type,
+            PointerType::Touch,
+            "the second touch is a touch"
+        );
+        let PointerEvent::Move(update) = pointer(TouchPhase::Moved, 5) else {
+            panic!("expected a move");
+        };
+        assert!(
+            update.current.buttons.contains(PointerButton::Primary),
+            "the button is held while in contact"
+        );
+        let PointerEvent::Up(up) = pointer(TouchPhase::Ended, 5) else {
+            panic!("expected an up");
+        };
+        assert_eq!(
+            (up.pointer.pointer_type, up.button),
+            (PointerType::Mouse, Some(PointerButton::Primary)),
+            "the lift releases the button"
+        );
+        let PointerEvent::Move(update) = pointer(TouchPhase::Moved, 6) else {
+            panic!("expected a move");
+        };
+        assert_eq!(
+            update.pointer.pointer_type,
+            PointerType::Touch,
+            "the remaining touch is not emulated"
+        );
+    }
+
+    /// Each press and release of a chord has the modifiers held at its own time.
+    #[test]
+    fn modifier_chord() {
+        let mut reducer = WindowEventReducer::default();
+        let button = |reducer: &mut WindowEventReducer, state, button| match reducer
+            .reduce(&mouse_input(state, button))
+        {
+            Some(WindowEventTranslation::Pointer(
+                PointerEvent::Down(event) | PointerEvent::Up(event),
+            )) => event,
+            other => panic!("expected a button event, got {other:?}"),
+        };
+        reducer.reduce(&modifiers_changed(ModifiersState::CONTROL));
+        let left = button(&mut reducer, ElementState::Pressed, MouseButton::Left);
+        assert!(left.has_modifier(Modifiers::CONTROL), "Ctrl+left");
+        reducer.reduce(&modifiers_changed(ModifiersState::empty()));
+        let right = button(&mut reducer, ElementState::Pressed, MouseButton::Right);
+        assert!(
+            !right.has_modifier(Modifiers::CONTROL),
+            "right is pressed after Ctrl is released"
+        );
+        assert!(
+            right.state.buttons.contains(PointerButton::Primary)
+                && right.state.buttons.contains(PointerButton::Secondary),
+            "both buttons are held"
+        );
+        assert!(
+            left.has_modifier(Modifiers::CONTROL),
+            "the left press keeps its modifiers"
+        );
+        reducer.reduce(&modifiers_changed(
+            ModifiersState::CONTROL | ModifiersState::SHIFT,
+        ));
+        let up = button(&mut reducer, ElementState::Released, MouseButton::Left);
+        assert!(
+            up.has_modifier(Modifiers::CONTROL | Modifiers::SHIFT),
+            "the release has the modifiers at its time"
+        );
+    }
+
+    /// A batch of taps and moves is counted as when the events are reduced one by one.
+    #[test]
+    fn batched_taps() {
+        let events = [
+            cursor_moved(1.0, 1.0),
+            mouse_input(ElementState::Pressed, MouseButton::Left),
+            cursor_moved(1.0, 1.0),
+            mouse_input(ElementState::Released, MouseButton::Left),
+            cursor_moved(2.0, 1.0),
+            mouse_input(ElementState::Pressed, MouseButton::Left),
+            mouse_input(ElementState::Released, MouseButton::Left),
+            cursor_moved(2.0, 1.0),
+            cursor_moved(2.0, 2.0),
+            mouse_input(ElementState::Pressed, MouseButton::Left),
+            mouse_input(ElementState::Released, MouseButton::Left),
+        ];
+        fn counts(translations: &[WindowEventTranslation]) -> Vec<(bool, u8)> {
+            translations
+                .iter()
+                .filter_map(|translation| match translation {
+                    WindowEventTranslation::Pointer(PointerEvent::Down(event)) => {
+                        Some((true, event.state.count))
+                    }
+                    WindowEventTranslation::Pointer(PointerEvent::Up(event)) => {
+                        Some((false, event.state.count))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+
+        // Deduplication is the only stage which merges moves.
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_dedupe_moves(true);
+        let mut batch = Vec::new();
+        for we in &events {
+            reducer.reduce_into(we, &mut batch);
+        }
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_dedupe_moves(true);
+        let single: Vec<_> = events.iter().filter_map(|we| reducer.reduce(we)).collect();
+
+        assert_eq!(
+            counts(&batch),
+            [
+                (true, 1),
+                (false, 1),
+                (true, 2),
+                (false, 2),
+                (true, 3),
+                (false, 3)
+            ],
+            "moves don't disturb the taps"
+        );
+        assert_eq!(
+            counts(&batch),
+            counts(&single),
+            "a batch is counted like single events"
+        );
+    }
+
+    /// Touch moves report their velocity, which is zero for the first move.
+    #[test]
+    fn touch_velocity() {
+        let mut reducer = WindowEventReducer::default();
+        let mut out = Vec::new();
+        reducer.reduce_into_at(
+            &touch(TouchPhase::Started, 1, 0.0, 0.0, None),
+            1_000_000_000,
+            &mut out,
+        );
+        let mut velocity = |phase, x, time| {
+            let mut out = Vec::new();
+            reducer.reduce_into_at(&touch(phase, 1, x, 0.0, None), time, &mut out);
+            pointer_update(out.pop()).velocity
+        };
+        let zero = Some(PhysicalPosition::new(0.0, 0.0));
+        assert_eq!(
+            velocity(TouchPhase::Moved, 2.0, 1_010_000_000),
+            zero,
+            "the first move has no velocity"
+        );
+        assert_eq!(
+            velocity(TouchPhase::Moved, 7.0, 1_020_000_000),
+            Some(PhysicalPosition::new(500.0, 0.0)),
+            "5 px in 10 ms"
+        );
+    }
+
+    /// The handled events are the ones which are translated.
+    #[test]
+    fn handles() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer.handles(&cursor_moved(1.0, 1.0)),
+            "cursor moves are handled"
+        );
+        assert!(
+            reducer.handles(&WindowEvent::Resized(PhysicalSize::new(1, 1))),
+            "resizes update the state"
+        );
+        assert!(
+            !reducer.handles(&WindowEvent::Focused(true)),
+            "focus changes are not handled"
+        );
+        let drop = WindowEvent::DroppedFile("file".into());
+        assert!(!reducer.handles(&drop), "file drops are disabled");
+        assert!(reducer.reduce(&drop).is_none(), "file drops are ignored");
+        reducer.set_file_drops(true);
+        assert!(reducer.handles(&drop), "file drops are enabled");
+        assert!(reducer.reduce(&drop).is_some(), "file drops are translated");
+    }
+
+    /// Hovering moves have no count, including the first move after entering.
+    #[test]
+    fn hover_count() {
+        let mut reducer = WindowEventReducer::default();
+        let hover = |reducer: &mut WindowEventReducer, x| {
+            pointer_update(reducer.reduce(&cursor_moved(x, 0.0)))
+                .current
+                .count
+        };
+        for _ in 0..2 {
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+            reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+        }
+        assert_eq!(hover(&mut reducer, 1.0), 0, "no count after a double click");
+        reducer.reduce(&cursor_left());
+        reducer.reduce(&cursor_entered());
+        let entered = pointer_update(reducer.reduce_all(&cursor_moved(2.0, 0.0)).pop());
+        assert_eq!(entered.current.count, 0, "no count after entering");
+    }
+
+    /// The first event has a positive time, and times don't go backwards.
+    #[test]
+    fn first_event_time() {
+        let mut reducer = WindowEventReducer::default();
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+        else {
+            panic!("expected a pointer down");
+        };
+        assert!(down.state.time > 0, "the first event has a positive time");
+        assert_eq!(down.state.count, 1, "the first click starts a sequence");
+
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+            reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left))
+        else {
+            panic!("expected a pointer up");
+        };
+        assert!(
+            up.state.time >= down.state.time,
+            "time doesn't go backwards"
+        );
+    }
+
+    /// Repeated moves to the same position are only dropped when deduplicating.
+    #[test]
+    fn dedupe_moves() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        assert!(
+            reducer.reduce(&cursor_moved(1.0, 2.0)).is_some(),
+            "repeated moves are kept by default"
+        );
+
+        reducer.set_dedupe_moves(true);
+        assert!(
+            reducer.reduce(&cursor_moved(1.0, 2.0)).is_some(),
+            "the first move is kept"
+        );
+        assert!(
+            reducer.reduce(&cursor_moved(1.0, 2.0)).is_none(),
+            "a repeated move is dropped"
+        );
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        assert!(
+            reducer.reduce(&cursor_moved(1.0, 2.0)).is_some(),
+            "a move with different buttons is kept"
+        );
+        assert!(
+            reducer.reduce(&cursor_moved(3.0, 2.0)).is_some(),
+            "a move to a new position is kept"
+        );
+    }
+
+    /// Pointer events carry the modifiers held when they occur.
+    #[test]
+    fn pointer_modifiers() {
+        use ui_events::keyboard::Modifiers;
+
+        fn modifiers(reducer: &mut WindowEventReducer, we: WindowEvent) -> Modifiers {
+            match reducer.reduce(&we) {
+                Some(WindowEventTranslation::Pointer(
+                    PointerEvent::Down(PointerButtonEvent { state, .. })
+                    | PointerEvent::Up(PointerButtonEvent { state, .. })
+                    | PointerEvent::Move(PointerUpdate { current: state, .. })
+                    | PointerEvent::Scroll(PointerScrollEvent { state, .. }),
+                )) => state.modifiers,
+                other => panic!("expected a pointer event with a state, got {other:?}"),
             }
-            PointerEvent::Cancel(p) => {
-                self.taps
-                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
-                PointerEvent::Cancel(p)
+        }
+
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            reducer
+                .reduce(&modifiers_changed(ModifiersState::SHIFT))
+                .is_none(),
+            "modifier changes produce nothing"
+        );
+        let shift = Modifiers::SHIFT;
+        let click = mouse_input(ElementState::Pressed, MouseButton::Left);
+        assert_eq!(modifiers(&mut reducer, click), shift, "a click");
+        let drag = cursor_moved(1.0, 2.0);
+        assert_eq!(modifiers(&mut reducer, drag), shift, "a drag");
+        let scroll = mouse_wheel(MouseScrollDelta::LineDelta(0.0, 1.0));
+        assert_eq!(modifiers(&mut reducer, scroll), shift, "a scroll");
+        let tap = touch(TouchPhase::Started, 1, 1.0, 1.0, None);
+        assert_eq!(modifiers(&mut reducer, tap), shift, "a touch");
+
+        reducer.reduce(&modifiers_changed(ModifiersState::empty()));
+        let release = mouse_input(ElementState::Released, MouseButton::Left);
+        assert_eq!(
+            modifiers(&mut reducer, release),
+            Modifiers::empty(),
+            "a release after shift was released"
+        );
+    }
+
+    /// Moving the window is tracked, and doesn't change pointer positions.
+    #[test]
+    fn window_moved() {
+        let mut reducer = WindowEventReducer::default();
+        assert_eq!(reducer.window_position(), None, "unknown by default");
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        let position = PhysicalPosition::new(100, 50);
+        assert!(
+            reducer.reduce(&WindowEvent::Moved(position)).is_none(),
+            "moving the window produces nothing"
+        );
+        assert_eq!(
+            reducer.window_position(),
+            Some(position),
+            "position is kept"
+        );
+        assert_eq!(
+            reducer.primary_pointer_state().position,
+            PhysicalPosition::new(1.0, 2.0),
+            "pointer positions are window relative"
+        );
+    }
+
+    /// The last translation and the primary pointer state can be polled.
+    #[test]
+    fn polling() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(reducer.last_event().is_none(), "nothing delivered yet");
+
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        reducer.reduce(&WindowEvent::Focused(true));
+        assert!(
+            matches!(
+                reducer.last_event(),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(..)))
+            ),
+            "the last translation is kept"
+        );
+        let state = reducer.primary_pointer_state();
+        assert_eq!(state.position, PhysicalPosition::new(1.0, 2.0), "position");
+        assert!(
+            state.buttons.contains(PointerButton::Primary),
+            "the held button"
+        );
+    }
+
+    /// Quadratic prediction follows a curved path, where linear prediction cuts the corner.
+    #[test]
+    fn prediction() {
+        use transform::{PredictionStrategy, Predictor};
+        use ui_events::pointer::PointerEventBuilder;
+
+        fn predict(strategy: PredictionStrategy) -> Vec<(f64, f64)> {
+            let mut predictor = Predictor::new(strategy, Duration::from_millis(10));
+            let mut out = Vec::new();
+            // A parabola, sampled every 10 ms.
+            for i in 0..4_u32 {
+                let x = f64::from(i);
+                let move_ = PointerEventBuilder::new()
+                    .position(x, x * x)
+                    .time(u64::from(i) * 10_000_000)
+                    .moved();
+                predictor.transform(move_.into(), &mut out);
             }
-            PointerEvent::Leave(p) => {
-                self.taps
-                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
-                PointerEvent::Leave(p)
+            out.into_iter()
+                .filter_map(|translation| pointer_update(Some(translation)).predicted.pop())
+                .map(|state| (state.position.x.round(), state.position.y.round()))
+                .collect()
+        }
+
+        assert_eq!(
+            predict(PredictionStrategy::Linear),
+            [(2.0, 2.0), (3.0, 7.0), (4.0, 14.0)],
+            "linear prediction continues the last velocity"
+        );
+        assert_eq!(
+            predict(PredictionStrategy::Quadratic),
+            [(2.0, 2.0), (3.0, 9.0), (4.0, 16.0)],
+            "quadratic prediction stays on the parabola"
+        );
+    }
+
+    /// The palm rejection stage places the right and bottom edges with the window size.
+    #[test]
+    fn palm_rejector() {
+        use transform::PalmRejector;
+        use ui_events::pointer::PointerEventBuilder;
+
+        fn tap(rejector: &mut PalmRejector, x: f64, duration: u64) -> PointerEvent {
+            let touch = || {
+                PointerEventBuilder::new()
+                    .pointer_type(PointerType::Touch)
+                    .position(x, 50.0)
+            };
+            let mut out = Vec::new();
+            rejector.transform(touch().time(1).down().into(), &mut out);
+            rejector.transform(touch().time(1 + duration).up().into(), &mut out);
+            match out.pop() {
+                Some(WindowEventTranslation::Pointer(e)) => e,
+                other => panic!("expected a pointer event, got {other:?}"),
             }
-            e @ (PointerEvent::Enter(..) | PointerEvent::Scroll(..)) => e,
         }
+
+        let config = PalmRejection {
+            edge_margin: 10.0,
+            min_duration: 100,
+        };
+        let mut rejector = PalmRejector::new(config, None);
+        assert!(
+            matches!(tap(&mut rejector, 5.0, 10), PointerEvent::Cancel(..)),
+            "a brief touch at the left edge is rejected"
+        );
+        assert!(
+            matches!(tap(&mut rejector, 5.0, 100), PointerEvent::Up(..)),
+            "a long touch at the left edge is accepted"
+        );
+        assert!(
+            matches!(tap(&mut rejector, 95.0, 10), PointerEvent::Up(..)),
+            "the right edge is unknown without a window size"
+        );
+        rejector.set_window_size(Some(PhysicalSize::new(100, 100)));
+        assert!(
+            matches!(tap(&mut rejector, 95.0, 10), PointerEvent::Cancel(..)),
+            "the window size places the right edge"
+        );
     }
 
-    /// Clear expired taps.
-    ///
-    /// `t` is the time of the last received event.
-    /// All events have the same time base on Android, so this is valid here.
-    fn clear_expired(&mut self, t: u64) {
-        self.taps.retain(
-            |TapState {
-                 down_time, up_time, ..
-             }| { down_time == up_time || (up_time + 500_000_000) > t },
+    /// Transforms run in order, and every translation they emit is delivered.
+    #[test]
+    fn transforms() {
+        let mut reducer = WindowEventReducer::default();
+        // Drop enters, and duplicate moves.
+        reducer.push_transform(
+            |event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>| match event {
+                WindowEventTranslation::Pointer(PointerEvent::Enter(..)) => {}
+                WindowEventTranslation::Pointer(PointerEvent::Move(update)) => {
+                    out.push(PointerEvent::Move(update.clone()).into());
+                    out.push(PointerEvent::Move(update).into());
+                }
+                event => out.push(event),
+            },
+        );
+        // Mark each translation, by counting it in the click count of moves.
+        reducer.push_transform(
+            |event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>| {
+                out.push(match event {
+                    WindowEventTranslation::Pointer(PointerEvent::Move(mut update)) => {
+                        update.current.count += 10;
+                        PointerEvent::Move(update).into()
+                    }
+                    event => event,
+                });
+            },
+        );
+
+        let mut out = Vec::new();
+        reducer.reduce_into(&cursor_entered(), &mut out);
+        assert!(out.is_empty(), "the first stage drops enters");
+
+        reducer.reduce_into(&cursor_moved(1.0, 2.0), &mut out);
+        assert_eq!(out.len(), 2, "the first stage duplicates moves");
+        for translation in out {
+            assert_eq!(
+                pointer_update(Some(translation)).current.count,
+                10,
+                "the second stage runs on each output of the first"
+            );
+        }
+
+        assert_eq!(
+            pointer_update(reducer.reduce(&cursor_moved(3.0, 2.0)))
+                .current
+                .count,
+            10,
+            "reduce returns the first translation"
+        );
+        reducer.clear_transforms();
+        let mut out = Vec::new();
+        reducer.reduce_into(&cursor_entered(), &mut out);
+        reducer.reduce_into(&cursor_moved(4.0, 2.0), &mut out);
+        assert!(
+            matches!(
+                out.as_slice(),
+                [
+                    WindowEventTranslation::Pointer(PointerEvent::Enter(..)),
+                    WindowEventTranslation::Pointer(PointerEvent::Move(update)),
+                ] if update.current.count == 0
+            ),
+            "cleared transforms don't run, so the enter isn't dropped and the move isn't marked"
         );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    // CI will fail unless cargo nextest can execute at least one test per workspace.
-    // Delete this dummy test once we have an actual real test.
+    /// Translations are attributed to the window that was set.
+    #[test]
+    fn window_id() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            matches!(
+                reducer.reduce_with_window_id(&cursor_moved(0.0, 0.0)),
+                Some((None, _))
+            ),
+            "no window by default"
+        );
+        let window_id = WindowId::from(7);
+        reducer.set_window_id(Some(window_id));
+        assert_eq!(reducer.window_id(), Some(window_id), "window is set");
+        assert!(
+            matches!(
+                reducer.reduce_with_window_id(&cursor_moved(1.0, 2.0)),
+                Some((Some(id), _)) if id == window_id
+            ),
+            "translation is attributed to the window"
+        );
+        assert!(
+            reducer
+                .reduce_with_window_id(&WindowEvent::Focused(true))
+                .is_none(),
+            "unhandled events produce nothing"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+        let restarted =
+            reducer.reduce_all_with_window_id(&touch(TouchPhase::Started, 1, 5.0, 5.0, None));
+        assert!(
+            matches!(
+                &restarted[..],
+                [
+                    (Some(cancel), WindowEventTranslation::Pointer(PointerEvent::Cancel(..))),
+                    (Some(down), WindowEventTranslation::Pointer(PointerEvent::Down(..))),
+                ] if *cancel == window_id && *down == window_id
+            ),
+            "every translation is attributed to the window, got {restarted:?}"
+        );
+    }
+
+    /// Disabled tap counting leaves every count at 0.
+    #[test]
+    fn tap_counting_disabled() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_tap_counting(false);
+        for _ in 0..2 {
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+                reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+            else {
+                panic!("expected a pointer down");
+            };
+            assert_eq!(down.state.count, 0, "clicks are not counted");
+            reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+        }
+        assert_eq!(
+            reducer.is_sequence_live(PointerId::PRIMARY, 0),
+            None,
+            "there is no sequence"
+        );
+    }
+
+    /// Click counts wrap back to 1 after the maximum.
+    #[test]
+    fn max_tap_count() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_max_tap_count(Some(3));
+        let counts: Vec<u8> = (0..7)
+            .map(|_| {
+                let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+                    reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left))
+                else {
+                    panic!("expected a pointer down");
+                };
+                reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+                down.state.count
+            })
+            .collect();
+        assert_eq!(
+            counts,
+            [1, 2, 3, 1, 2, 3, 1],
+            "counts wrap after the maximum"
+        );
+    }
+
+    /// A click sequence is live until its window expires.
+    #[test]
+    fn sequence_live() {
+        let mut reducer = WindowEventReducer::default();
+        assert_eq!(
+            reducer.is_sequence_live(PointerId::PRIMARY, 0),
+            None,
+            "no sequence before a click"
+        );
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+            reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left))
+        else {
+            panic!("expected a pointer up");
+        };
+        let time = up.state.time;
+        assert_eq!(
+            reducer.is_sequence_live(PointerId::PRIMARY, time),
+            Some(1),
+            "live right after the click"
+        );
+        assert_eq!(
+            reducer.is_sequence_live(PointerId::PRIMARY, time + 1_000_000_000),
+            None,
+            "expired after the window"
+        );
+    }
+
+    /// Non-finite positions don't poison the pointer state.
+    #[test]
+    fn non_finite_position() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        assert!(
+            reducer.reduce(&cursor_moved(f64::NAN, 2.0)).is_none(),
+            "a non-finite cursor move is dropped"
+        );
+        assert!(
+            reducer.reduce(&cursor_moved(3.0, f64::INFINITY)).is_none(),
+            "an infinite cursor move is dropped"
+        );
+        let update = pointer_update(reducer.reduce(&cursor_moved(4.0, 6.0)));
+        assert_eq!(
+            update.delta(),
+            PhysicalPosition::new(3.0, 4.0),
+            "delta is relative to the last finite position"
+        );
+
+        assert!(
+            reducer
+                .reduce(&touch(TouchPhase::Started, 0, f64::NAN, 0.0, None))
+                .is_none(),
+            "an untracked non-finite touch is dropped"
+        );
+        reducer.reduce(&touch(TouchPhase::Started, 1, 5.0, 5.0, None));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+            reducer.reduce(&touch(TouchPhase::Ended, 1, f64::NAN, f64::NAN, None))
+        else {
+            panic!("expected a touch up");
+        };
+        assert_eq!(
+            up.state.position,
+            PhysicalPosition::new(5.0, 5.0),
+            "a tracked touch falls back to its last position"
+        );
+    }
+
+    /// Pointer history is bounded and cleared when the pointer leaves.
+    #[test]
+    fn pointer_history() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_pointer_history_capacity(3);
+        for x in 0..5 {
+            reducer.reduce(&cursor_moved(f64::from(x), 0.0));
+        }
+        let xs: Vec<f64> = reducer
+            .pointer_history(PointerId::PRIMARY)
+            .map(|state| state.position.x)
+            .collect();
+        assert_eq!(xs, [2.0, 3.0, 4.0], "only the latest states are kept");
+
+        reducer.reduce(&cursor_left());
+        assert_eq!(
+            reducer.pointer_history(PointerId::PRIMARY).count(),
+            0,
+            "history is cleared on leave"
+        );
+
+        let touch_id = PointerId::new(1 + TOUCH_ID_OFFSET).unwrap();
+        reducer.reduce(&touch(TouchPhase::Started, 1, 0.0, 0.0, None));
+        reducer.reduce(&touch(TouchPhase::Moved, 1, 1.0, 0.0, None));
+        reducer.reduce(&touch(TouchPhase::Ended, 1, 1.0, 0.0, None));
+        assert_eq!(
+            reducer.pointer_history(touch_id).count(),
+            3,
+            "history of a touch is available after it ends"
+        );
+        reducer.reduce(&touch(TouchPhase::Started, 2, 0.0, 0.0, None));
+        assert_eq!(
+            reducer.pointer_history(touch_id).count(),
+            0,
+            "history of an ended touch is discarded when another starts"
+        );
+    }
+
+    /// Fractional line deltas are accumulated into whole notches.
+    #[test]
+    fn scroll_notches() {
+        let mut reducer = WindowEventReducer::default();
+        let mut notches = |delta| match reducer.reduce(&mouse_wheel(delta)) {
+            Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(event))) => event.notches,
+            other => panic!("expected a scroll, got {other:?}"),
+        };
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.0, 1.0)),
+            Some((0, 1)),
+            "a whole notch"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.5, -0.5)),
+            Some((0, 0)),
+            "half a notch"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.5, -0.75)),
+            Some((1, -1)),
+            "halves add up to a notch"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.0, -0.5)),
+            Some((0, 0)),
+            "most of a notch up"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.0, 0.5)),
+            Some((0, 0)),
+            "a reversal discards the notch up"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::LineDelta(0.0, -0.5)),
+            Some((0, 0)),
+            "no spurious notch up"
+        );
+        assert_eq!(
+            notches(MouseScrollDelta::PixelDelta(PhysicalPosition::new(
+                0.0, 10.0
+            ))),
+            None,
+            "pixel deltas have no notches"
+        );
+    }
+
+    /// Translations can be split into their halves and put back together.
+    #[test]
+    fn translation_conversions() {
+        let mut reducer = WindowEventReducer::default();
+        let t = reducer.reduce(&cursor_moved(1.0, 1.0)).unwrap();
+        let t = KeyboardEvent::try_from(t).expect_err("a move is not a keyboard event");
+        let e = PointerEvent::try_from(t).expect("a move is a pointer event");
+        assert!(
+            matches!(
+                WindowEventTranslation::from(e),
+                WindowEventTranslation::Pointer(PointerEvent::Move(..))
+            ),
+            "a pointer event converts back to a translation"
+        );
+    }
+
+    /// The earliest remaining touch becomes primary when the primary touch lifts.
     #[test]
-    fn dummy_test_until_we_have_a_real_test() {}
+    fn primary_touch() {
+        let mut reducer = WindowEventReducer::default();
+        assert_eq!(reducer.primary_touch(), None, "no touches");
+
+        reducer.reduce(&touch(TouchPhase::Started, 3, 1.0, 1.0, None));
+        assert!(
+            !reducer.primary_touch_changed(),
+            "the first touch is not a change"
+        );
+        reducer.reduce(&touch(TouchPhase::Started, 4, 9.0, 1.0, None));
+        reducer.reduce(&touch(TouchPhase::Started, 5, 19.0, 1.0, None));
+        assert_eq!(
+            reducer.primary_touch(),
+            PointerId::new(3 + TOUCH_ID_OFFSET),
+            "first touch is primary"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Ended, 3, 1.0, 1.0, None));
+        assert!(
+            reducer.primary_touch_changed(),
+            "lifting the primary changes it"
+        );
+        assert_eq!(
+            reducer.primary_touch(),
+            PointerId::new(4 + TOUCH_ID_OFFSET),
+            "next touch is primary"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Moved, 4, 9.0, 2.0, None));
+        assert!(
+            !reducer.primary_touch_changed(),
+            "the change is only reported once"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Ended, 6, 29.0, 1.0, None));
+        assert!(
+            !reducer.primary_touch_changed(),
+            "other touches don't change it"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Ended, 5, 19.0, 1.0, None));
+        reducer.reduce(&touch(TouchPhase::Ended, 4, 9.0, 2.0, None));
+        assert!(
+            !reducer.primary_touch_changed(),
+            "lifting the last touch is not a change"
+        );
+        assert_eq!(reducer.primary_touch(), None, "no touches left");
+    }
+
+    /// Small moves are smoothed by the jitter filter, and large moves are not.
+    #[test]
+    fn jitter_filter() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_jitter_filter(Some(JitterFilter {
+            amplitude: 4.0,
+            smoothing: 0.5,
+        }));
+        reducer.reduce(&cursor_moved(10.0, 10.0));
+        let tremor = pointer_update(reducer.reduce(&cursor_moved(12.0, 10.0)));
+        assert_eq!(
+            tremor.current.position,
+            PhysicalPosition::new(11.0, 10.0),
+            "a tremor is smoothed"
+        );
+        let deliberate = pointer_update(reducer.reduce(&cursor_moved(31.0, 10.0)));
+        assert_eq!(
+            deliberate.current.position,
+            PhysicalPosition::new(31.0, 10.0),
+            "a deliberate move is unchanged"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Started, 1, 50.0, 50.0, None));
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+            reducer.reduce(&touch(TouchPhase::Ended, 1, 50.0, 52.0, None))
+        else {
+            panic!("expected a pointer up");
+        };
+        assert_eq!(
+            up.state.position,
+            PhysicalPosition::new(50.0, 51.0),
+            "a trembling touch is smoothed"
+        );
+    }
+
+    /// A touch which restarts without lifting cancels its stale sequence.
+    #[test]
+    fn restarted_touch() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+        let mut out = Vec::new();
+        reducer.reduce_into(&touch(TouchPhase::Started, 1, 5.0, 5.0, None), &mut out);
+        assert!(
+            matches!(
+                &out[..],
+                [
+                    WindowEventTranslation::Pointer(PointerEvent::Cancel(..)),
+                    WindowEventTranslation::Pointer(PointerEvent::Down(..)),
+                ]
+            ),
+            "the stale touch is cancelled before the new one goes down, got {out:?}"
+        );
+        assert_eq!(reducer.active_touch_count(), 1, "one touch is tracked");
+
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&touch(TouchPhase::Started, 1, 7.0, 7.0, None))
+        else {
+            panic!("expected reduce to return the down of the restarted touch");
+        };
+        assert_eq!(
+            down.state.position,
+            PhysicalPosition::new(7.0, 7.0),
+            "the down is of the new touch"
+        );
+        assert_eq!(
+            reducer.active_touch_count(),
+            1,
+            "one touch is still tracked"
+        );
+    }
+
+    /// A move for a touch that never started starts it.
+    #[test]
+    fn orphan_touch_move() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Moved, 0, 1.0, 1.0, None)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Down(..)))
+            ),
+            "an orphan move goes down"
+        );
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Moved, 0, 2.0, 1.0, None)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Move(..)))
+            ),
+            "later moves are moves"
+        );
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+            reducer.reduce(&touch(TouchPhase::Ended, 0, 2.0, 1.0, None))
+        else {
+            panic!("expected a touch up");
+        };
+        assert_eq!(up.state.count, 1, "the synthesized down is counted");
+    }
+
+    /// Brief touches at the window edges are cancelled.
+    #[test]
+    fn palm_rejection() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_palm_rejection(Some(PalmRejection {
+            edge_margin: 10.0,
+            // Longer than any test should take.
+            min_duration: 60_000_000_000,
+        }));
+        reducer.reduce(&WindowEvent::Resized(PhysicalSize::new(100, 100)));
+
+        let mut end = |x, y| {
+            reducer.reduce(&touch(TouchPhase::Started, 0, x, y, None));
+            match reducer.reduce(&touch(TouchPhase::Ended, 0, x, y, None)) {
+                Some(WindowEventTranslation::Pointer(e)) => e,
+                other => panic!("expected a pointer event, got {other:?}"),
+            }
+        };
+        assert!(
+            matches!(end(5.0, 50.0), PointerEvent::Cancel(..)),
+            "left edge touch is rejected"
+        );
+        assert!(
+            matches!(end(50.0, 95.0), PointerEvent::Cancel(..)),
+            "bottom edge touch is rejected"
+        );
+        assert!(
+            matches!(end(50.0, 50.0), PointerEvent::Up(..)),
+            "central touch is accepted"
+        );
+        end(5.0, 50.0);
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&touch(TouchPhase::Started, 0, 5.0, 50.0, None))
+        else {
+            panic!("expected a touch down");
+        };
+        assert_eq!(down.state.count, 1, "a rejected touch isn't a tap");
+        reducer.reduce(&touch(TouchPhase::Cancelled, 0, 5.0, 50.0, None));
+
+        reducer.set_palm_rejection(Some(PalmRejection {
+            edge_margin: 10.0,
+            min_duration: 0,
+        }));
+        reducer.reduce(&touch(TouchPhase::Started, 0, 5.0, 50.0, None));
+        assert!(
+            matches!(
+                reducer.reduce(&touch(TouchPhase::Ended, 0, 5.0, 50.0, None)),
+                Some(WindowEventTranslation::Pointer(PointerEvent::Up(..)))
+            ),
+            "long edge touch is accepted"
+        );
+    }
+
+    /// Moves interleaved with resizes and scale factor changes use the latest
+    /// window size, and their positions stay physical.
+    #[test]
+    fn live_resize() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_palm_rejection(Some(PalmRejection {
+            edge_margin: 10.0,
+            // Longer than any test should take.
+            min_duration: 60_000_000_000,
+        }));
+        let mut end = |size, scale_factor, x| {
+            // `ScaleFactorChanged` can't be constructed, see `set_scale_factor`.
+            reducer.set_scale_factor(scale_factor);
+            reducer.reduce(&WindowEvent::Resized(size));
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Move(update))) =
+                reducer.reduce(&cursor_moved(x, 50.0))
+            else {
+                panic!("expected a move");
+            };
+            assert_eq!(
+                update.current.position,
+                PhysicalPosition::new(x, 50.0),
+                "positions are not affected by resizes or scale factor changes"
+            );
+            assert_eq!(
+                reducer.scale_factor(),
+                Some(scale_factor),
+                "the scale factor is updated before the move"
+            );
+            reducer.reduce(&touch(TouchPhase::Started, 0, x, 50.0, None));
+            match reducer.reduce(&touch(TouchPhase::Ended, 0, x, 50.0, None)) {
+                Some(WindowEventTranslation::Pointer(e)) => e,
+                other => panic!("expected a pointer event, got {other:?}"),
+            }
+        };
+        assert!(
+            matches!(
+                end(PhysicalSize::new(100, 100), 1.0, 95.0),
+                PointerEvent::Cancel(..)
+            ),
+            "right edge touch is rejected"
+        );
+        assert!(
+            matches!(
+                end(PhysicalSize::new(200, 100), 2.0, 95.0),
+                PointerEvent::Up(..)
+            ),
+            "the edge moved with the resize, whatever the scale factor"
+        );
+        assert!(
+            matches!(
+                end(PhysicalSize::new(100, 100), 1.0, 95.0),
+                PointerEvent::Cancel(..)
+            ),
+            "the edge moved back"
+        );
+    }
+
+    /// Moves report where the drag started while a button is held.
+    #[test]
+    fn down_position() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        reducer.reduce(&cursor_moved(5.0, 5.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Right));
+        reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Left));
+        let update = pointer_update(reducer.reduce(&cursor_moved(9.0, 9.0)));
+        assert_eq!(
+            update.down_position,
+            Some(PhysicalPosition::new(1.0, 2.0)),
+            "the drag started where the first button was pressed"
+        );
+        reducer.reduce(&mouse_input(ElementState::Released, MouseButton::Right));
+        let update = pointer_update(reducer.reduce(&cursor_moved(10.0, 10.0)));
+        assert_eq!(update.down_position, None, "no button is held");
+
+        reducer.reduce(&touch(TouchPhase::Started, 0, 3.0, 3.0, None));
+        let update = pointer_update(reducer.reduce(&touch(TouchPhase::Moved, 0, 4.0, 4.0, None)));
+        assert_eq!(
+            update.down_position,
+            Some(PhysicalPosition::new(3.0, 3.0)),
+            "the touch started where it went down"
+        );
+    }
+
+    /// Raw keyboard mode reports character keys by their physical position.
+    #[test]
+    fn raw_keyboard() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_raw_keyboard(true);
+        // The key at Q on an AZERTY layout, which is labelled A.
+        let event = reducer.translate_key(key_event(Code::KeyQ, "a", KeyState::Down), Some("a"));
+        assert_eq!(
+            event.key,
+            Key::Character("q".into()),
+            "the key is derived from the physical key"
+        );
+        let enter = KeyboardEvent {
+            key: Key::Named(NamedKey::Enter),
+            code: Code::Enter,
+            ..KeyboardEvent::default()
+        };
+        assert_eq!(
+            reducer.translate_key(enter, None).key,
+            Key::Named(NamedKey::Enter),
+            "named keys keep their logical key"
+        );
+
+        reducer.set_raw_keyboard(false);
+        let event = reducer.translate_key(key_event(Code::KeyQ, "a", KeyState::Down), Some("a"));
+        assert_eq!(
+            event.key,
+            Key::Character("a".into()),
+            "text input keeps the logical key"
+        );
+    }
+
+    /// Input method preedit marks keyboard input as composing until it is committed.
+    #[test]
+    fn ime_composition() {
+        use alloc::string::String;
+
+        let mut reducer = WindowEventReducer::default();
+        let is_composing = |reducer: &mut WindowEventReducer| {
+            reducer
+                .translate_key(key_event(Code::KeyA, "a", KeyState::Down), Some("a"))
+                .is_composing
+        };
+        reducer.reduce(&WindowEvent::Ime(Ime::Enabled));
+        assert!(
+            !is_composing(&mut reducer),
+            "enabling doesn't start a composition"
+        );
+        reducer.reduce(&WindowEvent::Ime(Ime::Preedit(
+            String::from("a"),
+            Some((1, 1)),
+        )));
+        assert!(
+            is_composing(&mut reducer),
+            "keys during a preedit are composing"
+        );
+        reducer.reduce(&WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+        assert!(
+            !is_composing(&mut reducer),
+            "empty preedit ends the composition"
+        );
+        reducer.reduce(&WindowEvent::Ime(Ime::Preedit(
+            String::from("に"),
+            Some((3, 3)),
+        )));
+        assert!(is_composing(&mut reducer), "a new preedit composes again");
+        reducer.reduce(&WindowEvent::Ime(Ime::Commit(String::from("に"))));
+        assert!(!is_composing(&mut reducer), "commit ends the composition");
+        reducer.set_raw_keyboard(true);
+        reducer.reduce(&WindowEvent::Ime(Ime::Preedit(
+            String::from("a"),
+            Some((1, 1)),
+        )));
+        assert!(!is_composing(&mut reducer), "raw keys are never composing");
+    }
+
+    /// Problems are reported by `try_reduce`, and the event is still processed.
+    #[test]
+    fn try_reduce() {
+        let mut reducer = WindowEventReducer::default();
+        assert!(
+            matches!(
+                reducer.try_reduce(&cursor_moved(1.0, 2.0)),
+                Ok(Some(WindowEventTranslation::Pointer(PointerEvent::Move(
+                    ..
+                ))))
+            ),
+            "a valid event is translated"
+        );
+        assert_eq!(
+            reducer.try_reduce(&cursor_moved(f64::NAN, 2.0)).err(),
+            Some(ReduceError::NonFinitePosition),
+            "a non-finite position is an error"
+        );
+        assert_eq!(
+            reducer
+                .try_reduce(&mouse_input(ElementState::Pressed, MouseButton::Other(100)))
+                .err(),
+            Some(ReduceError::UnmappedButton(MouseButton::Other(100))),
+            "an unmapped button is an error"
+        );
+
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+        assert_eq!(
+            reducer
+                .try_reduce(&touch(TouchPhase::Ended, 1, f64::INFINITY, 1.0, None))
+                .err(),
+            Some(ReduceError::NonFinitePosition),
+            "a non-finite touch is an error"
+        );
+        assert_eq!(reducer.active_touch_count(), 0, "the touch still ended");
+    }
+
+    /// Mouse buttons without a representation are reported without a button.
+    #[test]
+    fn unmapped_mouse_button() {
+        let mut reducer = WindowEventReducer::default();
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Other(100)))
+        else {
+            panic!("expected the unmapped press to go down");
+        };
+        assert_eq!(down.button, None, "unmapped press has no button");
+        assert!(down.state.buttons.is_empty(), "unmapped button isn't held");
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) = reducer.reduce(
+            &mouse_input(ElementState::Released, MouseButton::Other(100)),
+        ) else {
+            panic!("expected the unmapped release to go up");
+        };
+        assert_eq!(up.button, None, "unmapped release has no button");
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Middle))
+        else {
+            panic!("expected a pointer down");
+        };
+        assert_eq!(
+            down.button_or_primary(),
+            PointerButton::Auxiliary,
+            "mapped press has a button"
+        );
+    }
+
+    /// Every mappable mouse button is reported, and tracked in the state.
+    #[test]
+    fn mouse_buttons() {
+        let mut reducer = WindowEventReducer::default();
+        for (winit_button, button) in [
+            (MouseButton::Left, PointerButton::Primary),
+            (MouseButton::Right, PointerButton::Secondary),
+            (MouseButton::Middle, PointerButton::Auxiliary),
+            (MouseButton::Back, PointerButton::X1),
+            (MouseButton::Forward, PointerButton::X2),
+            (MouseButton::Other(6), PointerButton::B7),
+            (MouseButton::Other(31), PointerButton::B32),
+        ] {
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+                reducer.reduce(&mouse_input(ElementState::Pressed, winit_button))
+            else {
+                panic!("expected a pointer down for {winit_button:?}");
+            };
+            assert_eq!(down.button, Some(button), "{winit_button:?} is mapped");
+            assert_eq!(
+                reducer.primary_pointer_state().buttons,
+                PointerButtons::from(button),
+                "{winit_button:?} is held"
+            );
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Up(up))) =
+                reducer.reduce(&mouse_input(ElementState::Released, winit_button))
+            else {
+                panic!("expected a pointer up for {winit_button:?}");
+            };
+            assert_eq!(up.button, Some(button), "{winit_button:?} is released");
+            assert!(
+                reducer.primary_pointer_state().buttons.is_empty(),
+                "{winit_button:?} is no longer held"
+            );
+        }
+        for winit_button in [MouseButton::Other(5), MouseButton::Other(32)] {
+            assert!(
+                matches!(
+                    reducer.reduce(&mouse_input(ElementState::Pressed, winit_button)),
+                    Some(WindowEventTranslation::Pointer(PointerEvent::Down(
+                        PointerButtonEvent { button: None, .. }
+                    )))
+                ),
+                "{winit_button:?} has no button"
+            );
+            assert!(
+                reducer.primary_pointer_state().buttons.is_empty(),
+                "{winit_button:?} is not held"
+            );
+        }
+    }
+
+    /// Scroll deltas are scaled by the sensitivity.
+    #[test]
+    fn scroll_sensitivity() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_scroll_sensitivity(2.0);
+        let mut scroll = |delta| match reducer.reduce(&mouse_wheel(delta)) {
+            Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(event))) => event,
+            other => panic!("expected a scroll, got {other:?}"),
+        };
+        let line = scroll(MouseScrollDelta::LineDelta(0.5, -1.0));
+        assert_eq!(
+            line.delta,
+            ScrollDelta::LineDelta(1.0, -2.0),
+            "line delta is scaled"
+        );
+        assert_eq!(
+            line.raw_delta,
+            ScrollDelta::LineDelta(0.5, -1.0),
+            "raw delta is not scaled"
+        );
+        assert_eq!(line.notches, Some((0, -1)), "notches are not scaled");
+        assert_eq!(
+            scroll(MouseScrollDelta::PixelDelta(PhysicalPosition::new(
+                3.0, 4.0
+            )))
+            .delta,
+            ScrollDelta::PixelDelta(PhysicalPosition::new(6.0, 8.0)),
+            "pixel delta is scaled"
+        );
+    }
+
+    /// The horizontal axis of line deltas is kept, and scaled by the tilt sensitivity.
+    #[test]
+    fn wheel_tilt() {
+        let mut reducer = WindowEventReducer::default();
+        let scroll =
+            |reducer: &mut WindowEventReducer, delta| match reducer.reduce(&mouse_wheel(delta)) {
+                Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(event))) => event.delta,
+                other => panic!("expected a scroll, got {other:?}"),
+            };
+        let tilt = MouseScrollDelta::LineDelta(-1.0, 0.0);
+        assert_eq!(
+            scroll(&mut reducer, tilt),
+            ScrollDelta::LineDelta(-1.0, 0.0),
+            "the tilt is kept"
+        );
+        reducer.set_wheel_tilt_sensitivity(-2.0);
+        assert_eq!(
+            scroll(&mut reducer, MouseScrollDelta::LineDelta(-1.0, 1.0)),
+            ScrollDelta::LineDelta(2.0, 1.0),
+            "the tilt is reversed and scaled"
+        );
+        let pan = PhysicalPosition::new(3.0, 0.0);
+        assert_eq!(
+            scroll(&mut reducer, MouseScrollDelta::PixelDelta(pan)),
+            ScrollDelta::PixelDelta(pan),
+            "touchpad scrolls are not affected"
+        );
+    }
+
+    /// Pixel deltas are physical, and are not converted with the scale factor,
+    /// even when it changes mid-scroll.
+    #[test]
+    fn pixel_scroll_is_physical() {
+        let mut reducer = WindowEventReducer::default();
+        let delta = PhysicalPosition::new(3.0, -7.5);
+        for scale_factor in [1.0, 2.0, 1.5] {
+            // `ScaleFactorChanged` can't be constructed, see `set_scale_factor`.
+            reducer.set_scale_factor(scale_factor);
+            let Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(event))) =
+                reducer.reduce(&mouse_wheel(MouseScrollDelta::PixelDelta(delta)))
+            else {
+                panic!("expected a scroll");
+            };
+            assert_eq!(
+                event.delta,
+                ScrollDelta::PixelDelta(delta),
+                "pixel deltas are passed through at any scale factor"
+            );
+        }
+        assert_eq!(
+            reducer.scale_factor(),
+            Some(1.5),
+            "the latest scale factor is kept"
+        );
+    }
+
+    /// The pointer goes idle when the last held button is released.
+    #[test]
+    fn went_idle() {
+        let mut reducer = WindowEventReducer::default();
+        let mut went_idle = |we: WindowEvent| match reducer.reduce(&we) {
+            Some(WindowEventTranslation::Pointer(
+                event @ (PointerEvent::Down(..) | PointerEvent::Up(..)),
+            )) => event.went_idle(),
+            other => panic!("expected a button event, got {other:?}"),
+        };
+        assert!(!went_idle(mouse_input(
+            ElementState::Pressed,
+            MouseButton::Left
+        )));
+        assert!(!went_idle(mouse_input(
+            ElementState::Pressed,
+            MouseButton::Right
+        )));
+        assert!(
+            !went_idle(mouse_input(ElementState::Released, MouseButton::Left)),
+            "a button is still held"
+        );
+        assert!(
+            went_idle(mouse_input(ElementState::Released, MouseButton::Right)),
+            "no buttons are held"
+        );
+        assert!(
+            !went_idle(touch(TouchPhase::Started, 0, 0.0, 0.0, None)),
+            "a touch doesn't go idle when it starts, although it holds no buttons"
+        );
+        assert!(
+            went_idle(touch(TouchPhase::Ended, 0, 0.0, 0.0, None)),
+            "a touch goes idle when it ends"
+        );
+    }
+
+    /// A duplicated end of a touch is dropped.
+    #[test]
+    fn duplicate_touch_end() {
+        let mut reducer = WindowEventReducer::default();
+        let ends = [TouchPhase::Started, TouchPhase::Ended, TouchPhase::Ended]
+            .into_iter()
+            .filter_map(|phase| reducer.reduce(&touch(phase, 1, 1.0, 1.0, None)))
+            .filter(|translation| {
+                matches!(
+                    translation,
+                    WindowEventTranslation::Pointer(PointerEvent::Up(..))
+                )
+            })
+            .count();
+        assert_eq!(ends, 1, "only one up");
+        assert!(
+            reducer
+                .reduce(&touch(TouchPhase::Cancelled, 1, 1.0, 1.0, None))
+                .is_none(),
+            "a cancellation of an ended touch is dropped"
+        );
+        let Some(WindowEventTranslation::Pointer(PointerEvent::Down(down))) =
+            reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None))
+        else {
+            panic!("expected a down");
+        };
+        assert_eq!(down.state.count, 2, "the tap sequence is intact");
+    }
 }
@@ -0,0 +1,138 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Metrics of the input processed by a [`WindowEventReducer`].
+//!
+//! These help to understand the input load of an application, for example
+//! to tune [move deduplication].
+//!
+//! [`WindowEventReducer`]: crate::WindowEventReducer
+//! [move deduplication]: crate::WindowEventReducer::set_dedupe_moves
+
+use ui_events::pointer::PointerEvent;
+
+use crate::WindowEventTranslation;
+
+/// Counts of the translations delivered by a [`WindowEventReducer`](crate::WindowEventReducer).
+///
+/// See [`WindowEventReducer::metrics`](crate::WindowEventReducer::metrics).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// Number of keyboard events.
+    pub keyboard: u64,
+    /// Number of [`PointerEvent::Down`]s.
+    pub pointer_down: u64,
+    /// Number of [`PointerEvent::Up`]s.
+    pub pointer_up: u64,
+    /// Number of [`PointerEvent::Move`]s.
+    pub pointer_move: u64,
+    /// Number of [`PointerEvent::Cancel`]s.
+    pub pointer_cancel: u64,
+    /// Number of [`PointerEvent::Enter`]s.
+    pub pointer_enter: u64,
+    /// Number of [`PointerEvent::Leave`]s.
+    pub pointer_leave: u64,
+    /// Number of [`PointerEvent::Scroll`]s.
+    pub pointer_scroll: u64,
+    /// Number of gesture events.
+    pub gesture: u64,
+    /// Number of file drag and drop events.
+    pub file_drop: u64,
+    /// Largest number of pointers active at the same time.
+    ///
+    /// This counts touches, and the mouse while a button is held.
+    pub max_simultaneous_pointers: usize,
+    /// Sum of the intervals between moves, in nanoseconds.
+    move_interval_total: u64,
+    /// Number of intervals between moves.
+    move_intervals: u64,
+    /// Time of the last move.
+    last_move_time: Option<u64>,
+}
+
+impl Metrics {
+    /// The average interval between consecutive [`PointerEvent::Move`]s, in nanoseconds.
+    ///
+    /// This is `None` until there have been two moves.
+    pub fn average_move_interval(&self) -> Option<u64> {
+        (self.move_intervals > 0).then(|| self.move_interval_total / self.move_intervals)
+    }
+
+    /// Record a delivered translation, while `pointers` are active.
+    pub(crate) fn record(&mut self, translation: &WindowEventTranslation, pointers: usize) {
+        self.max_simultaneous_pointers = self.max_simultaneous_pointers.max(pointers);
+        let e = match translation {
+            WindowEventTranslation::Keyboard(..) => {
+                self.keyboard += 1;
+                return;
+            }
+            WindowEventTranslation::Gesture(..) => {
+                self.gesture += 1;
+                return;
+            }
+            #[cfg(feature = "std")]
+            WindowEventTranslation::FileDrop(..) => {
+                self.file_drop += 1;
+                return;
+            }
+            WindowEventTranslation::Pointer(e) => e,
+        };
+        match e {
+            PointerEvent::Down(..) => self.pointer_down += 1,
+            PointerEvent::Up(..) => self.pointer_up += 1,
+            PointerEvent::Move(update) => {
+                self.pointer_move += 1;
+                let time = update.current.time;
+                if let Some(last) = self.last_move_time.replace(time) {
+                    self.move_interval_total += time.saturating_sub(last);
+                    self.move_intervals += 1;
+                }
+            }
+            PointerEvent::Cancel(..) => self.pointer_cancel += 1,
+            PointerEvent::Enter(..) => self.pointer_enter += 1,
+            PointerEvent::Leave(..) => self.pointer_leave += 1,
+            PointerEvent::Scroll(..) => self.pointer_scroll += 1,
+        }
+    }
+}
+
+// The tests use default reducers, whose clock needs `std`.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::test_util::*;
+    use crate::WindowEventReducer;
+    use winit::event::{ElementState, MouseButton, TouchPhase};
+
+    /// Delivered translations are counted.
+    #[test]
+    fn metrics() {
+        let mut reducer = WindowEventReducer::default();
+        reducer.reduce(&cursor_entered());
+        reducer.reduce(&cursor_moved(1.0, 2.0));
+        assert_eq!(
+            reducer.metrics().average_move_interval(),
+            None,
+            "no interval after one move"
+        );
+        reducer.reduce(&cursor_moved(3.0, 2.0));
+        reducer.reduce(&mouse_input(ElementState::Pressed, MouseButton::Left));
+        reducer.reduce(&touch(TouchPhase::Started, 1, 1.0, 1.0, None));
+        reducer.reduce(&touch(TouchPhase::Started, 2, 9.0, 1.0, None));
+
+        let metrics = reducer.metrics();
+        assert_eq!(metrics.pointer_enter, 1, "one enter");
+        assert_eq!(metrics.pointer_move, 2, "two moves");
+        assert_eq!(metrics.pointer_down, 3, "three downs");
+        assert_eq!(
+            metrics.max_simultaneous_pointers, 3,
+            "a button and two touches"
+        );
+        assert!(
+            metrics.average_move_interval().is_some(),
+            "an interval after two moves"
+        );
+
+        reducer.reset_metrics();
+        assert_eq!(reducer.metrics().pointer_down, 0, "metrics are reset");
+    }
+}
@@ -8,9 +8,18 @@ use winit::event::MouseButton;
 
 /// Try to make a [`PointerButton`] from a [`MouseButton`].
 ///
-/// Because values of [`MouseButton::Other`] can start at 0, they are mapped
-/// to the arbitrary buttons B7..B32.
-/// Values greater than 25 will not be mapped.
+/// [`MouseButton::Back`] and [`MouseButton::Forward`] are [`PointerButton::X1`]
+/// and [`PointerButton::X2`].
+/// Values of [`MouseButton::Other`] are zero based button indices, so 6 to 31
+/// are mapped to the arbitrary buttons B7..B32.
+/// Other values overlap the named buttons or are out of range, and will not be mapped.
+///
+/// There is deliberately no fallback for unmapped buttons: [`PointerButton`] is a
+/// single bit of a [`PointerButtons`](ui_events::pointer::PointerButtons) set, and
+/// all 32 bits are taken by the numbered buttons, so there is no room for an
+/// `Other` button which could stay in the set while held. The values of
+/// [`MouseButton::Other`] are also platform button codes, so a
+/// catch-all would not identify the same button on every platform.
 pub fn try_from_winit_button(b: MouseButton) -> Option<PointerButton> {
     Some(match b {
         MouseButton::Left => PointerButton::Primary,
@@ -0,0 +1,110 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! An asynchronous [`Stream`] of translated events.
+//!
+//! This bridges the callback based [`winit`] event loop to asynchronous consumers:
+//! push each [`WindowEvent`] into the sender from the event loop, and consume the
+//! [`WindowEventTranslation`]s from a [`WindowEventStream`] in a task.
+
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_core::Stream;
+use winit::event::WindowEvent;
+
+use crate::{WindowEventReducer, WindowEventTranslation};
+
+/// A [`Stream`] of [`WindowEventTranslation`]s for the [`WindowEvent`]s of a window.
+///
+/// [`WindowEvent`]s which don't produce a translation are consumed silently,
+/// and every translation emitted by the reducer's transforms is delivered.
+/// The stream ends when all senders are dropped.
+#[derive(Debug)]
+pub struct WindowEventStream {
+    reducer: WindowEventReducer,
+    receiver: UnboundedReceiver<WindowEvent>,
+    /// Translations not yet delivered, in reverse order.
+    pending: Vec<WindowEventTranslation>,
+}
+
+impl WindowEventStream {
+    /// Make a new stream, reducing events with `reducer`.
+    ///
+    /// Returns the sender to push [`WindowEvent`]s into, along with the stream.
+    pub fn new(reducer: WindowEventReducer) -> (UnboundedSender<WindowEvent>, Self) {
+        let (sender, receiver) = unbounded();
+        (
+            sender,
+            Self {
+                reducer,
+                receiver,
+                pending: Vec::new(),
+            },
+        )
+    }
+
+    /// The [`WindowEventReducer`] of this stream.
+    pub fn reducer(&self) -> &WindowEventReducer {
+        &self.reducer
+    }
+
+    /// The [`WindowEventReducer`] of this stream, for configuration.
+    pub fn reducer_mut(&mut self) -> &mut WindowEventReducer {
+        &mut self.reducer
+    }
+}
+
+impl Stream for WindowEventStream {
+    type Item = WindowEventTranslation;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(translation) = this.pending.pop() {
+                return Poll::Ready(Some(translation));
+            }
+            match Pin::new(&mut this.receiver).poll_next(cx) {
+                Poll::Ready(Some(we)) => {
+                    this.reducer.reduce_into(&we, &mut this.pending);
+                    this.pending.reverse();
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+// The tests use default reducers, whose clock needs `std`.
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use alloc::vec::Vec;
+    use ui_events::pointer::PointerEvent;
+
+    /// Translations are streamed, and ignored events are skipped.
+    #[test]
+    fn stream() {
+        let (sender, stream) = WindowEventStream::new(WindowEventReducer::default());
+        sender.unbounded_send(cursor_entered()).unwrap();
+        sender.unbounded_send(WindowEvent::RedrawRequested).unwrap();
+        sender.unbounded_send(cursor_moved(1.0, 2.0)).unwrap();
+        drop(sender);
+
+        let translations: Vec<_> = futures_executor::block_on_stream(stream).collect();
+        assert!(
+            matches!(
+                translations[..],
+                [
+                    WindowEventTranslation::Pointer(PointerEvent::Enter(..)),
+                    WindowEventTranslation::Pointer(PointerEvent::Move(..)),
+                ]
+            ),
+            "expected an enter and a move, got {translations:?}"
+        );
+    }
+}
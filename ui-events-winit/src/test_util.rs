@@ -0,0 +1,84 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Builders for synthetic [`winit`] events.
+//!
+//! These fill in the fields that are irrelevant to [`WindowEventReducer`](crate::WindowEventReducer),
+//! such as the [`DeviceId`], to make writing tests and benchmarks less verbose.
+//!
+//! A [`KeyEvent`] can't be constructed outside of [`winit`], so [`keyboard_input`]
+//! wraps an existing one.
+
+use winit::{
+    dpi::PhysicalPosition,
+    event::{
+        DeviceId, ElementState, Force, KeyEvent, MouseButton, MouseScrollDelta, Touch, TouchPhase,
+        WindowEvent,
+    },
+    keyboard::ModifiersState,
+};
+
+/// Make a [`WindowEvent::CursorEntered`].
+pub fn cursor_entered() -> WindowEvent {
+    WindowEvent::CursorEntered {
+        device_id: DeviceId::dummy(),
+    }
+}
+
+/// Make a [`WindowEvent::CursorLeft`].
+pub fn cursor_left() -> WindowEvent {
+    WindowEvent::CursorLeft {
+        device_id: DeviceId::dummy(),
+    }
+}
+
+/// Make a [`WindowEvent::ModifiersChanged`] to `state`.
+pub fn modifiers_changed(state: ModifiersState) -> WindowEvent {
+    WindowEvent::ModifiersChanged(state.into())
+}
+
+/// Make a [`WindowEvent::CursorMoved`] to `(x, y)`.
+pub fn cursor_moved(x: f64, y: f64) -> WindowEvent {
+    WindowEvent::CursorMoved {
+        device_id: DeviceId::dummy(),
+        position: PhysicalPosition::new(x, y),
+    }
+}
+
+/// Make a [`WindowEvent::MouseInput`].
+pub fn mouse_input(state: ElementState, button: MouseButton) -> WindowEvent {
+    WindowEvent::MouseInput {
+        device_id: DeviceId::dummy(),
+        state,
+        button,
+    }
+}
+
+/// Make a [`WindowEvent::MouseWheel`] with [`TouchPhase::Moved`].
+pub fn mouse_wheel(delta: MouseScrollDelta) -> WindowEvent {
+    WindowEvent::MouseWheel {
+        device_id: DeviceId::dummy(),
+        delta,
+        phase: TouchPhase::Moved,
+    }
+}
+
+/// Make a [`WindowEvent::Touch`] for touch `id` at `(x, y)`.
+pub fn touch(phase: TouchPhase, id: u64, x: f64, y: f64, force: Option<Force>) -> WindowEvent {
+    WindowEvent::Touch(Touch {
+        device_id: DeviceId::dummy(),
+        phase,
+        location: PhysicalPosition::new(x, y),
+        force,
+        id,
+    })
+}
+
+/// Make a [`WindowEvent::KeyboardInput`] from a [`KeyEvent`].
+pub fn keyboard_input(event: KeyEvent) -> WindowEvent {
+    WindowEvent::KeyboardInput {
+        device_id: DeviceId::dummy(),
+        event,
+        is_synthetic: false,
+    }
+}
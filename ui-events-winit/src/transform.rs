@@ -0,0 +1,363 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Composable stages which transform translated events.
+//!
+//! Add an [`EventTransform`] to a [`WindowEventReducer`] with
+//! [`WindowEventReducer::push_transform`], and it is run on every translation
+//! the reducer produces, after the stages added before it.
+//!
+//! Of the built-in processing, [`DedupeMoves`], [`Predictor`] and [`PalmRejector`]
+//! are stages. The filters which need the raw input, or the state of the reducer,
+//! are not: the jitter filter and the coordinate transform change positions before
+//! they are stored in the pointer state, and three finger gesture recognition tags
+//! the translations it withholds and emits, which a stage can't do.
+//! These run before the stages, and are configured on the reducer.
+//!
+//! [`WindowEventReducer`]: crate::WindowEventReducer
+//! [`WindowEventReducer::push_transform`]: crate::WindowEventReducer::push_transform
+
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+use core::time::Duration;
+
+use ui_events::pointer::{
+    PointerButtonEvent, PointerEvent, PointerId, PointerState, PointerType, PointerUpdate,
+};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+
+use crate::{PalmRejection, WindowEventTranslation};
+
+/// A stage which consumes a translation, and emits any number of translations.
+pub trait EventTransform {
+    /// Transform `event`, pushing the resulting translations onto `out`.
+    ///
+    /// Push nothing to drop `event`, or several translations to expand it.
+    fn transform(&mut self, event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>);
+}
+
+impl<F> EventTransform for F
+where
+    F: FnMut(WindowEventTranslation, &mut Vec<WindowEventTranslation>),
+{
+    fn transform(&mut self, event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>) {
+        self(event, out);
+    }
+}
+
+/// Drops pointer moves which don't change the pointer state.
+///
+/// A [`PointerEvent::Move`] is dropped when its position, buttons, and modifiers
+/// are the same as those of the last move of that pointer since it entered.
+/// This is what [`WindowEventReducer::set_dedupe_moves`] enables.
+///
+/// [`WindowEventReducer::set_dedupe_moves`]: crate::WindowEventReducer::set_dedupe_moves
+#[derive(Clone, Debug, Default)]
+pub struct DedupeMoves {
+    /// State of the last move of each pointer.
+    last: Vec<(Option<PointerId>, PointerState)>,
+}
+
+impl EventTransform for DedupeMoves {
+    fn transform(&mut self, event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>) {
+        match &event {
+            WindowEventTranslation::Pointer(PointerEvent::Move(PointerUpdate {
+                pointer,
+                current,
+                ..
+            })) => {
+                let same = |last: &PointerState| {
+                    last.position == current.position
+                        && last.buttons == current.buttons
+                        && last.modifiers == current.modifiers
+                };
+                match self
+                    .last
+                    .iter_mut()
+                    .find(|(id, _)| *id == pointer.pointer_id)
+                {
+                    Some((_, last)) if same(last) => return,
+                    Some((_, last)) => *last = current.clone(),
+                    None => self.last.push((pointer.pointer_id, current.clone())),
+                }
+            }
+            WindowEventTranslation::Pointer(
+                PointerEvent::Enter(pointer, _)
+                | PointerEvent::Leave(pointer, _)
+                | PointerEvent::Cancel(pointer),
+            ) => {
+                self.last.retain(|(id, _)| *id != pointer.pointer_id);
+            }
+            _ => {}
+        }
+        out.push(event);
+    }
+}
+
+/// How a [`Predictor`] extrapolates the path of a pointer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PredictionStrategy {
+    /// Continue at the velocity between the last two states.
+    #[default]
+    Linear,
+    /// Continue along the curve through the last three states, following their
+    /// velocity and acceleration, so curved strokes stay curved.
+    ///
+    /// This falls back to [`Linear`](Self::Linear) until three states are known.
+    Quadratic,
+}
+
+/// Time and position of a pointer state, for prediction.
+type Sample = (u64, f64, f64);
+
+/// Fills the [`predicted`](PointerUpdate::predicted) states of pointer moves.
+///
+/// Each [`PointerEvent::Move`] gets one predicted state, `lookahead` after its
+/// current state, extrapolated from the recent states of that pointer with the
+/// [`PredictionStrategy`]. Moves are predicted once at least two states of the
+/// pointer are known since it last went down, went up, entered, left, or was
+/// cancelled, and moves which already have predicted states are left as they are.
+///
+/// Add it to a reducer with [`WindowEventReducer::push_transform`].
+///
+/// [`WindowEventReducer::push_transform`]: crate::WindowEventReducer::push_transform
+#[derive(Clone, Debug)]
+pub struct Predictor {
+    /// How to extrapolate.
+    strategy: PredictionStrategy,
+    /// How far ahead to predict, in nanoseconds.
+    lookahead: u64,
+    /// Time and position of the last three states of each pointer, oldest first.
+    history: Vec<(Option<PointerId>, Vec<Sample>)>,
+}
+
+impl Predictor {
+    /// Number of states kept per pointer.
+    const HISTORY: usize = 3;
+
+    /// A predictor which predicts `lookahead` into the future with `strategy`.
+    ///
+    /// A lookahead of about one frame hides most of the latency of drawing.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(strategy: PredictionStrategy, lookahead: Duration) -> Self {
+        Self {
+            strategy,
+            lookahead: lookahead.as_nanos() as u64,
+            history: Vec::new(),
+        }
+    }
+
+    /// The predicted position at `time`, if there is enough history.
+    #[allow(clippy::cast_precision_loss)]
+    fn predict(&self, samples: &[Sample], time: u64) -> Option<(f64, f64)> {
+        let dt = |a: u64, b: u64| b.saturating_sub(a) as f64;
+        let velocity = |(t0, x0, y0): Sample, (t1, x1, y1): Sample| {
+            let dt = dt(t0, t1);
+            (dt > 0.0).then(|| ((x1 - x0) / dt, (y1 - y0) / dt))
+        };
+        let &[.., s1, s2] = samples else {
+            return None;
+        };
+        let (vx, vy) = velocity(s1, s2)?;
+        let h = dt(s2.0, time);
+        let (mut x, mut y) = (s2.1 + vx * h, s2.2 + vy * h);
+        if let (PredictionStrategy::Quadratic, &[s0, _, _]) = (self.strategy, samples) {
+            // Newton's form of the quadratic through the three samples.
+            if let Some((ux, uy)) = velocity(s0, s1) {
+                let span = dt(s0.0, s2.0);
+                let k = h * (h + dt(s1.0, s2.0)) / span;
+                x += (vx - ux) * k;
+                y += (vy - uy) * k;
+            }
+        }
+        Some((x, y))
+    }
+}
+
+impl EventTransform for Predictor {
+    fn transform(&mut self, event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>) {
+        match event {
+            WindowEventTranslation::Pointer(PointerEvent::Move(mut update)) => {
+                let index = match self
+                    .history
+                    .iter()
+                    .position(|(id, _)| *id == update.pointer.pointer_id)
+                {
+                    Some(index) => index,
+                    None => {
+                        self.history.push((update.pointer.pointer_id, Vec::new()));
+                        self.history.len() - 1
+                    }
+                };
+                let samples = &mut self.history[index].1;
+                for state in update.coalesced.iter().chain([&update.current]) {
+                    if samples.last().is_some_and(|&(time, ..)| time >= state.time) {
+                        continue;
+                    }
+                    if samples.len() == Self::HISTORY {
+                        samples.remove(0);
+                    }
+                    samples.push((state.time, state.position.x, state.position.y));
+                }
+                let time = update.current.time + self.lookahead;
+                if update.predicted.is_empty() {
+                    if let Some((x, y)) = self.predict(&self.history[index].1, time) {
+                        let mut predicted = update.current.clone();
+                        predicted.time = time;
+                        predicted.position.x = x;
+                        predicted.position.y = y;
+                        update.predicted.push(predicted);
+                    }
+                }
+                out.push(PointerEvent::Move(update).into());
+            }
+            WindowEventTranslation::Pointer(
+                PointerEvent::Down(PointerButtonEvent { pointer, .. })
+                | PointerEvent::Up(PointerButtonEvent { pointer, .. })
+                | PointerEvent::Enter(pointer, _)
+                | PointerEvent::Leave(pointer, _)
+                | PointerEvent::Cancel(pointer),
+            ) => {
+                self.history.retain(|(id, _)| *id != pointer.pointer_id);
+                out.push(event);
+            }
+            event => out.push(event),
+        }
+    }
+}
+
+/// Cancels brief touches at the window edges, as configured by a [`PalmRejection`].
+///
+/// The [`PointerEvent::Up`] of a touch or pen which went down in the
+/// [`edge_margin`](PalmRejection::edge_margin), and lifts sooner than the
+/// [`min_duration`](PalmRejection::min_duration), is replaced by a [`PointerEvent::Cancel`].
+/// The right and bottom edges are only considered once the window size is known.
+/// Mouse events are passed through.
+///
+/// This is what [`WindowEventReducer::set_palm_rejection`] enables, which keeps the
+/// window size up to date and runs it before gesture recognition and mouse emulation.
+///
+/// [`WindowEventReducer::set_palm_rejection`]: crate::WindowEventReducer::set_palm_rejection
+#[derive(Clone, Debug)]
+pub struct PalmRejector {
+    /// Margin and duration of rejected touches.
+    config: PalmRejection,
+    /// Size of the window, if known.
+    window_size: Option<PhysicalSize<u32>>,
+    /// Pointers which went down in the margin, with the time they went down.
+    at_edge: Vec<(Option<PointerId>, u64)>,
+}
+
+impl PalmRejector {
+    /// A palm rejector with `config`, in a window of `window_size`, if it is known.
+    pub fn new(config: PalmRejection, window_size: Option<PhysicalSize<u32>>) -> Self {
+        Self {
+            config,
+            window_size,
+            at_edge: Vec::new(),
+        }
+    }
+
+    /// Set the size of the window, which places the right and bottom edges.
+    pub fn set_window_size(&mut self, window_size: Option<PhysicalSize<u32>>) {
+        self.window_size = window_size;
+    }
+
+    /// Returns `true` if `position` is in the edge margin.
+    fn is_at_edge(&self, position: PhysicalPosition<f64>) -> bool {
+        let margin = self.config.edge_margin;
+        position.x < margin
+            || position.y < margin
+            || self.window_size.is_some_and(|size| {
+                position.x > f64::from(size.width) - margin
+                    || position.y > f64::from(size.height) - margin
+            })
+    }
+
+    /// Replace the up of a rejected touch with a cancel.
+    pub(crate) fn filter(&mut self, event: WindowEventTranslation) -> WindowEventTranslation {
+        match &event {
+            WindowEventTranslation::Pointer(PointerEvent::Down(PointerButtonEvent {
+                pointer,
+                state,
+                ..
+            })) if pointer.pointer_type != PointerType::Mouse => {
+                self.at_edge.retain(|(id, _)| *id != pointer.pointer_id);
+                if self.is_at_edge(state.position) {
+                    self.at_edge.push((pointer.pointer_id, state.time));
+                }
+            }
+            WindowEventTranslation::Pointer(PointerEvent::Up(PointerButtonEvent {
+                pointer,
+                state,
+                ..
+            })) if pointer.pointer_type != PointerType::Mouse => {
+                if let Some(index) = self
+                    .at_edge
+                    .iter()
+                    .position(|(id, _)| *id == pointer.pointer_id)
+                {
+                    let (_, down_time) = self.at_edge.swap_remove(index);
+                    if state.time.saturating_sub(down_time) < self.config.min_duration {
+                        return PointerEvent::Cancel(*pointer).into();
+                    }
+                }
+            }
+            WindowEventTranslation::Pointer(
+                PointerEvent::Leave(pointer, _) | PointerEvent::Cancel(pointer),
+            ) => {
+                self.at_edge.retain(|(id, _)| *id != pointer.pointer_id);
+            }
+            _ => {}
+        }
+        event
+    }
+}
+
+impl EventTransform for PalmRejector {
+    fn transform(&mut self, event: WindowEventTranslation, out: &mut Vec<WindowEventTranslation>) {
+        out.push(self.filter(event));
+    }
+}
+
+/// The configured stages of a [`WindowEventReducer`](crate::WindowEventReducer).
+#[derive(Default)]
+pub(crate) struct Transforms {
+    /// Built-in move deduplication, run before the other stages.
+    pub(crate) dedupe_moves: Option<DedupeMoves>,
+    /// Stages added by the user, in order.
+    pub(crate) stages: Vec<Box<dyn EventTransform + Send>>,
+    /// Buffer for the output of a stage.
+    scratch: Vec<WindowEventTranslation>,
+}
+
+impl fmt::Debug for Transforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transforms")
+            .field("dedupe_moves", &self.dedupe_moves)
+            .field("stages", &self.stages.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transforms {
+    /// Run `event` through every stage, pushing the results onto `out`.
+    pub(crate) fn run(
+        &mut self,
+        event: WindowEventTranslation,
+        out: &mut Vec<WindowEventTranslation>,
+    ) {
+        let start = out.len();
+        if let Some(dedupe) = &mut self.dedupe_moves {
+            dedupe.transform(event, out);
+        } else {
+            out.push(event);
+        }
+        for stage in &mut self.stages {
+            self.scratch.extend(out.drain(start..));
+            for event in self.scratch.drain(..) {
+                stage.transform(event, out);
+            }
+        }
+    }
+}
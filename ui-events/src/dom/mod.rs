@@ -0,0 +1,197 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Conversion of pointer events into the shape of DOM events.
+//!
+//! This is for bridging to layout engines and renderers with HTML semantics,
+//! which expect the fields of the DOM `PointerEvent` and `WheelEvent` interfaces.
+
+use keyboard_types::Modifiers;
+
+use crate::pointer::{
+    PointerButton, PointerButtonEvent, PointerEvent, PointerInfo, PointerScrollEvent, PointerState,
+    PointerType, PointerUpdate,
+};
+use crate::ScrollDelta;
+
+/// Buttons in the order of their bits in [`DomPointerEvent::buttons`].
+const DOM_BUTTONS: [PointerButton; 6] = [
+    PointerButton::Primary,
+    PointerButton::Secondary,
+    PointerButton::Auxiliary,
+    PointerButton::X1,
+    PointerButton::X2,
+    PointerButton::PenEraser,
+];
+
+/// A [`PointerEvent`] with the fields of a DOM `PointerEvent` or `WheelEvent`.
+///
+/// Positions and pixel deltas are converted into CSS pixels with the scale factor
+/// given to [`DomPointerEvent::new`]. Fields which a [`PointerEvent`] doesn't carry,
+/// such as the position of a [`PointerEvent::Leave`], are zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DomPointerEvent {
+    /// The DOM event type, such as `"pointerdown"` or `"wheel"`.
+    pub event_type: &'static str,
+    /// The DOM pointer type, `"mouse"`, `"pen"`, `"touch"`, or `""` if unknown.
+    pub pointer_type: &'static str,
+    /// Whether this is the primary pointer.
+    pub is_primary: bool,
+    /// Horizontal position, in CSS pixels.
+    pub client_x: f64,
+    /// Vertical position, in CSS pixels.
+    pub client_y: f64,
+    /// The button which changed, `0` for the main button, or `-1` if none did.
+    pub button: i16,
+    /// Bitmask of the held buttons, starting with `1` for the main button.
+    pub buttons: u16,
+    /// Whether <kbd>Ctrl</kbd> is held.
+    pub ctrl_key: bool,
+    /// Whether <kbd>Shift</kbd> is held.
+    pub shift_key: bool,
+    /// Whether <kbd>Alt</kbd> is held.
+    pub alt_key: bool,
+    /// Whether <kbd>Meta</kbd> is held.
+    pub meta_key: bool,
+    /// Pressure, from `0.0` to `1.0`.
+    pub pressure: f32,
+    /// Horizontal scroll delta of a `"wheel"` event, in the unit of the [`delta_mode`](Self::delta_mode).
+    pub delta_x: f64,
+    /// Vertical scroll delta of a `"wheel"` event, in the unit of the [`delta_mode`](Self::delta_mode).
+    pub delta_y: f64,
+    /// Unit of the scroll deltas, `0` for CSS pixels, `1` for lines, and `2` for pages.
+    pub delta_mode: u32,
+}
+
+impl DomPointerEvent {
+    /// Convert `event`, with `scale_factor` physical pixels per CSS pixel.
+    pub fn new(event: &PointerEvent, scale_factor: f64) -> Self {
+        let (event_type, pointer, button, state) = match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button,
+                pointer,
+                state,
+            }) => ("pointerdown", pointer, dom_button(*button), Some(state)),
+            PointerEvent::Up(PointerButtonEvent {
+                button,
+                pointer,
+                state,
+            }) => ("pointerup", pointer, dom_button(*button), Some(state)),
+            PointerEvent::Move(PointerUpdate {
+                pointer, current, ..
+            }) => ("pointermove", pointer, -1, Some(current)),
+            PointerEvent::Scroll(PointerScrollEvent { pointer, state, .. }) => {
+                ("wheel", pointer, -1, Some(state))
+            }
+            PointerEvent::Cancel(pointer) => ("pointercancel", pointer, -1, None),
+            PointerEvent::Enter(pointer, state) => ("pointerenter", pointer, -1, Some(state)),
+            PointerEvent::Leave(pointer, state) => ("pointerleave", pointer, -1, Some(state)),
+        };
+        let mut dom = Self {
+            event_type,
+            pointer_type: dom_pointer_type(pointer),
+            is_primary: pointer.is_primary_pointer(),
+            client_x: 0.0,
+            client_y: 0.0,
+            button,
+            buttons: 0,
+            ctrl_key: false,
+            shift_key: false,
+            alt_key: false,
+            meta_key: false,
+            pressure: 0.0,
+            delta_x: 0.0,
+            delta_y: 0.0,
+            delta_mode: 0,
+        };
+        if let Some(state) = state {
+            dom.set_state(state, scale_factor);
+        }
+        if let PointerEvent::Scroll(PointerScrollEvent { delta, .. }) = event {
+            (dom.delta_x, dom.delta_y, dom.delta_mode) = match *delta {
+                ScrollDelta::PixelDelta(p) => (p.x / scale_factor, p.y / scale_factor, 0),
+                ScrollDelta::LineDelta(x, y) => (f64::from(x), f64::from(y), 1),
+                ScrollDelta::PageDelta(x, y) => (f64::from(x), f64::from(y), 2),
+            };
+        }
+        dom
+    }
+
+    /// Set the fields which come from the pointer state.
+    #[allow(clippy::cast_possible_truncation)]
+    fn set_state(&mut self, state: &PointerState, scale_factor: f64) {
+        self.client_x = state.position.x / scale_factor;
+        self.client_y = state.position.y / scale_factor;
+        self.buttons = DOM_BUTTONS
+            .iter()
+            .filter(|&&button| state.buttons.contains(button))
+            .fold(0, |buttons, &button| buttons | button as u16);
+        self.ctrl_key = state.modifiers.contains(Modifiers::CONTROL);
+        self.shift_key = state.modifiers.contains(Modifiers::SHIFT);
+        self.alt_key = state.modifiers.contains(Modifiers::ALT);
+        self.meta_key = state.modifiers.contains(Modifiers::META);
+        self.pressure = state.pressure;
+    }
+}
+
+/// The DOM `button` of a press or release, where a contact is the main button.
+fn dom_button(button: Option<PointerButton>) -> i16 {
+    match button.unwrap_or(PointerButton::Primary) {
+        PointerButton::Primary => 0,
+        PointerButton::Auxiliary => 1,
+        PointerButton::Secondary => 2,
+        PointerButton::X1 => 3,
+        PointerButton::X2 => 4,
+        PointerButton::PenEraser => 5,
+        _ => -1,
+    }
+}
+
+/// The DOM `pointerType` of a pointer.
+fn dom_pointer_type(pointer: &PointerInfo) -> &'static str {
+    match pointer.pointer_type {
+        PointerType::Mouse => "mouse",
+        PointerType::Pen => "pen",
+        PointerType::Touch => "touch",
+        PointerType::Unknown => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::{PointerButtons, PointerEventBuilder};
+
+    /// Fields are mapped to their DOM equivalents, in CSS pixels.
+    #[test]
+    fn dom_pointer_event() {
+        let mut buttons = PointerButtons::new();
+        buttons.insert(PointerButton::Auxiliary);
+        let down = PointerEventBuilder::new()
+            .position(20.0, 40.0)
+            .button(PointerButton::Secondary)
+            .buttons(buttons)
+            .modifiers(Modifiers::CONTROL | Modifiers::META)
+            .down();
+        let dom = DomPointerEvent::new(&down, 2.0);
+        assert_eq!(
+            (dom.event_type, dom.pointer_type, dom.is_primary),
+            ("pointerdown", "mouse", true),
+            "the type of the event and pointer"
+        );
+        assert_eq!((dom.client_x, dom.client_y), (10.0, 20.0), "CSS pixels");
+        assert_eq!((dom.button, dom.buttons), (2, 2 | 4), "the buttons");
+        assert!(
+            dom.ctrl_key && dom.meta_key && !dom.shift_key && !dom.alt_key,
+            "the modifiers"
+        );
+
+        let wheel = PointerEventBuilder::new().scroll(ScrollDelta::LineDelta(0.0, 3.0));
+        let dom = DomPointerEvent::new(&wheel, 2.0);
+        assert_eq!(
+            (dom.event_type, dom.button, dom.delta_y, dom.delta_mode),
+            ("wheel", -1, 3.0, 1),
+            "a wheel event in lines"
+        );
+    }
+}
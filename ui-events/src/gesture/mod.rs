@@ -0,0 +1,115 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Gesture Event Types
+
+use core::num::NonZeroU64;
+
+use crate::pointer::{PointerInfo, PointerState};
+
+/// An identifier correlating a recognized gesture with the pointer events that produced it.
+///
+/// Event sources which recognize gestures from pointer input assign this when a
+/// candidate gesture starts, and report it for its pointer events, so that
+/// consumers can receive both and reconcile them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GestureId(NonZeroU64);
+
+impl GestureId {
+    /// Make a new `GestureId` from a `u64`.
+    #[inline(always)]
+    pub fn new(n: u64) -> Option<Self> {
+        NonZeroU64::new(n).map(GestureId)
+    }
+}
+
+/// An event representing a smart zoom request.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmartZoomEvent {
+    /// Identity of the pointer.
+    pub pointer: PointerInfo,
+    /// The state of the pointer (i.e. position, modifiers, etc.).
+    pub state: PointerState,
+}
+
+/// The phase of a continuous gesture, such as a pinch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GesturePhase {
+    /// The gesture began.
+    Started,
+    /// The gesture continued.
+    Changed,
+    /// The gesture ended, and its effect should be kept.
+    Ended,
+    /// The gesture was cancelled, and its effect should be undone.
+    Cancelled,
+}
+
+/// An event representing a step of a pinch, usually on a touchpad.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinchEvent {
+    /// Identity of the pointer.
+    pub pointer: PointerInfo,
+    /// The state of the pointer (i.e. position, modifiers, etc.).
+    pub state: PointerState,
+    /// The change of scale since the previous step.
+    ///
+    /// The scale is multiplied by `1.0 + delta`, so a positive delta zooms in.
+    pub delta: f64,
+    /// The phase of the pinch.
+    pub phase: GesturePhase,
+}
+
+/// The direction of a swipe.
+///
+/// This is the direction the fingers moved in, in a Y-down coordinate system,
+/// so [`Up`](Self::Up) is towards the top of the window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwipeDirection {
+    /// Towards negative x.
+    Left,
+    /// Towards positive x.
+    Right,
+    /// Towards negative y.
+    Up,
+    /// Towards positive y.
+    Down,
+}
+
+/// A gesture recognized by the platform or the event source.
+///
+/// Gestures are kept separate from [`PointerEvent`](crate::pointer::PointerEvent)
+/// so that consumers only interested in standard pointer events can ignore them.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GestureEvent {
+    /// A smart zoom was requested at the pointer location.
+    ///
+    /// On macOS this is usually a double tap with two fingers on the trackpad,
+    /// and it is commonly used to zoom to fit an element under the pointer
+    /// or to reset any zoom.
+    SmartZoom(SmartZoomEvent),
+    /// A step of a pinch to zoom at the pointer location.
+    ///
+    /// Accumulate the deltas of a pinch from [`GesturePhase::Started`] until it
+    /// has [`GesturePhase::Ended`] or is [`GesturePhase::Cancelled`].
+    Pinch(PinchEvent),
+    /// Three fingers tapped together.
+    ThreeFingerTap {
+        /// Correlation with the pointer events of the fingers.
+        id: GestureId,
+    },
+    /// Three fingers swiped together.
+    ThreeFingerSwipe {
+        /// Correlation with the pointer events of the fingers.
+        id: GestureId,
+        /// The direction the fingers moved in.
+        direction: SwipeDirection,
+    },
+}
@@ -3,7 +3,268 @@
 
 //! Keyboard Types
 
+extern crate alloc;
+use alloc::string::{String, ToString};
+
 pub use keyboard_types::*;
 
 #[cfg(target_os = "android")]
 pub mod android;
+
+/// Convenience methods for classifying a [`KeyboardEvent`].
+pub trait KeyboardEventExt {
+    /// Returns `true` if this is a press requesting to cancel or close, usually <kbd>Escape</kbd>.
+    ///
+    /// On macOS, <kbd>Cmd</kbd>+<kbd>.</kbd> is also a cancel.
+    /// Presses during composition are never a cancel.
+    fn is_cancel(&self) -> bool;
+
+    /// Returns `true` if this is a press requesting to submit or accept, usually <kbd>Enter</kbd>.
+    ///
+    /// Presses with modifiers held, such as <kbd>Shift</kbd>+<kbd>Enter</kbd> which
+    /// often inserts a line break, and presses during composition are never a submit.
+    fn is_submit(&self) -> bool;
+
+    /// Returns `true` if this is a press which should insert text.
+    ///
+    /// This is the case when the key is a printable character, and no command
+    /// modifier (<kbd>Ctrl</kbd>, <kbd>Alt</kbd>, or <kbd>Meta</kbd>) is held.
+    /// <kbd>Shift</kbd> and <kbd>Alt Graph</kbd> are not command modifiers.
+    /// Presses during composition are never text input, as the input method
+    /// delivers their text.
+    fn is_text_input(&self) -> bool;
+
+    /// Format the modifiers and key of this event as a shortcut, like `Ctrl+Shift+K`.
+    ///
+    /// This is for displaying key bindings, for example in menus.
+    /// The modifier names and order follow the platform conventions, so
+    /// <kbd>Meta</kbd> is `Cmd` on macOS, `Win` on Windows, and `Super` elsewhere.
+    /// Letters are in upper case, and a space is `Space`.
+    fn to_shortcut_string(&self) -> String;
+
+    /// Returns the modifier of this key, if it is a modifier key like <kbd>Ctrl</kbd>.
+    ///
+    /// The [`state`](KeyboardEvent::state) tells whether the modifier was pressed or
+    /// released, so UIs can give feedback for modifiers distinctly from other keys.
+    /// For lock keys like <kbd>Caps Lock</kbd>, this is the lock which may have toggled.
+    fn toggled_modifier(&self) -> Option<Modifiers>;
+}
+
+/// Names of the modifiers in shortcuts, in the platform's order.
+#[cfg(target_os = "macos")]
+const SHORTCUT_MODIFIERS: [(Modifiers, &str); 4] = [
+    (Modifiers::CONTROL, "Ctrl"),
+    (Modifiers::ALT, "Option"),
+    (Modifiers::SHIFT, "Shift"),
+    (Modifiers::META, "Cmd"),
+];
+
+/// Names of the modifiers in shortcuts, in the platform's order.
+#[cfg(not(target_os = "macos"))]
+const SHORTCUT_MODIFIERS: [(Modifiers, &str); 4] = [
+    (Modifiers::CONTROL, "Ctrl"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::SHIFT, "Shift"),
+    #[cfg(target_os = "windows")]
+    (Modifiers::META, "Win"),
+    #[cfg(not(target_os = "windows"))]
+    (Modifiers::META, "Super"),
+];
+
+impl KeyboardEventExt for KeyboardEvent {
+    fn is_cancel(&self) -> bool {
+        if self.state != KeyState::Down || self.is_composing {
+            return false;
+        }
+        match &self.key {
+            Key::Named(NamedKey::Escape) => self.modifiers.is_empty(),
+            #[cfg(target_os = "macos")]
+            Key::Character(c) if c == "." => self.modifiers == Modifiers::META,
+            _ => false,
+        }
+    }
+
+    fn is_submit(&self) -> bool {
+        self.state == KeyState::Down
+            && !self.is_composing
+            && self.key == Key::Named(NamedKey::Enter)
+            && self.modifiers.is_empty()
+    }
+
+    fn is_text_input(&self) -> bool {
+        self.state == KeyState::Down
+            && !self.is_composing
+            && !self
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+            && matches!(&self.key, Key::Character(c) if !c.chars().any(char::is_control))
+    }
+
+    fn toggled_modifier(&self) -> Option<Modifiers> {
+        let Key::Named(key) = self.key else {
+            return None;
+        };
+        Some(match key {
+            NamedKey::Alt => Modifiers::ALT,
+            NamedKey::AltGraph => Modifiers::ALT_GRAPH,
+            NamedKey::CapsLock => Modifiers::CAPS_LOCK,
+            NamedKey::Control => Modifiers::CONTROL,
+            NamedKey::Fn => Modifiers::FN,
+            NamedKey::FnLock => Modifiers::FN_LOCK,
+            NamedKey::Meta => Modifiers::META,
+            NamedKey::NumLock => Modifiers::NUM_LOCK,
+            NamedKey::ScrollLock => Modifiers::SCROLL_LOCK,
+            NamedKey::Shift => Modifiers::SHIFT,
+            NamedKey::Symbol => Modifiers::SYMBOL,
+            NamedKey::SymbolLock => Modifiers::SYMBOL_LOCK,
+            // These are legacy names of Meta.
+            #[allow(deprecated)]
+            NamedKey::Hyper | NamedKey::Super => Modifiers::META,
+            _ => return None,
+        })
+    }
+
+    fn to_shortcut_string(&self) -> String {
+        let mut shortcut = String::new();
+        for (modifier, name) in SHORTCUT_MODIFIERS {
+            if self.modifiers.contains(modifier) {
+                shortcut.push_str(name);
+                shortcut.push('+');
+            }
+        }
+        match &self.key {
+            Key::Character(c) if c == " " => shortcut.push_str("Space"),
+            Key::Character(c) => shortcut.extend(c.chars().flat_map(char::to_uppercase)),
+            key => shortcut.push_str(&key.to_string()),
+        }
+        shortcut
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: Key, modifiers: Modifiers) -> KeyboardEvent {
+        KeyboardEvent {
+            state: KeyState::Down,
+            key,
+            modifiers,
+            ..Default::default()
+        }
+    }
+
+    /// Escape and Enter are cancel and submit, unless modified.
+    #[test]
+    fn cancel_and_submit() {
+        let escape = press(Key::Named(NamedKey::Escape), Modifiers::empty());
+        assert!(escape.is_cancel(), "escape is a cancel");
+        assert!(!escape.is_submit(), "escape is not a submit");
+
+        let enter = press(Key::Named(NamedKey::Enter), Modifiers::empty());
+        assert!(enter.is_submit(), "enter is a submit");
+        assert!(!enter.is_cancel(), "enter is not a cancel");
+
+        let shift_enter = press(Key::Named(NamedKey::Enter), Modifiers::SHIFT);
+        assert!(!shift_enter.is_submit(), "shift+enter is not a submit");
+
+        let released = KeyboardEvent {
+            state: KeyState::Up,
+            ..escape.clone()
+        };
+        assert!(!released.is_cancel(), "releasing escape is not a cancel");
+
+        let composing = KeyboardEvent {
+            is_composing: true,
+            ..enter
+        };
+        assert!(
+            !composing.is_submit(),
+            "enter while composing is not a submit"
+        );
+    }
+
+    /// Modifier keys report the modifier they toggle.
+    #[test]
+    fn toggled_modifier() {
+        let ctrl = press(Key::Named(NamedKey::Control), Modifiers::CONTROL);
+        assert_eq!(
+            ctrl.toggled_modifier(),
+            Some(Modifiers::CONTROL),
+            "pressing ctrl"
+        );
+        let released = KeyboardEvent {
+            state: KeyState::Up,
+            modifiers: Modifiers::empty(),
+            ..ctrl
+        };
+        assert_eq!(
+            (released.toggled_modifier(), released.state),
+            (Some(Modifiers::CONTROL), KeyState::Up),
+            "releasing ctrl"
+        );
+        let k = press(Key::Character("k".into()), Modifiers::CONTROL);
+        assert_eq!(k.toggled_modifier(), None, "a regular key");
+    }
+
+    /// Shortcuts are formatted with the platform's modifier names.
+    #[test]
+    fn shortcut_string() {
+        let k = press(
+            Key::Character("k".into()),
+            Modifiers::SHIFT | Modifiers::CONTROL,
+        );
+        assert_eq!(
+            k.to_shortcut_string(),
+            "Ctrl+Shift+K",
+            "modifiers are ordered"
+        );
+
+        let enter = press(Key::Named(NamedKey::Enter), Modifiers::empty());
+        assert_eq!(enter.to_shortcut_string(), "Enter", "a named key");
+
+        let space = press(Key::Character(" ".into()), Modifiers::ALT);
+        #[cfg(not(target_os = "macos"))]
+        assert_eq!(space.to_shortcut_string(), "Alt+Space", "a space");
+        #[cfg(target_os = "macos")]
+        assert_eq!(space.to_shortcut_string(), "Option+Space", "a space");
+
+        let meta = press(Key::Character("s".into()), Modifiers::META);
+        #[cfg(target_os = "macos")]
+        assert_eq!(meta.to_shortcut_string(), "Cmd+S", "command on macOS");
+        #[cfg(target_os = "windows")]
+        assert_eq!(meta.to_shortcut_string(), "Win+S", "the Windows key");
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        assert_eq!(meta.to_shortcut_string(), "Super+S", "the super key");
+    }
+
+    /// Printable characters are text input, unless a command modifier is held.
+    #[test]
+    fn text_input() {
+        let character = |c: &str, modifiers| press(Key::Character(c.into()), modifiers);
+        assert!(
+            character("a", Modifiers::empty()).is_text_input(),
+            "a letter"
+        );
+        assert!(
+            character("A", Modifiers::SHIFT).is_text_input(),
+            "a shifted letter"
+        );
+        assert!(
+            character("@", Modifiers::ALT_GRAPH).is_text_input(),
+            "a character composed with Alt Graph"
+        );
+        assert!(
+            !character("c", Modifiers::CONTROL).is_text_input(),
+            "a shortcut is a command"
+        );
+        assert!(
+            !character("\u{7f}", Modifiers::empty()).is_text_input(),
+            "a control character is not printable"
+        );
+        assert!(
+            !press(Key::Named(NamedKey::Enter), Modifiers::empty()).is_text_input(),
+            "a named key is not text"
+        );
+    }
+}
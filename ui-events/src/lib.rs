@@ -11,14 +11,21 @@
 //!
 //! - Pointer events (down/move/up, pressure, tilt, etc.)
 //! - Keyboard events (key codes, modifiers, location)
+//! - Gesture events (smart zoom, pinch, three-finger tap and swipe)
+//! - Tagging events with context for routing, with [`Tagged`]
 //!
 //! For integration with [`winit`], use the companion [`ui-events-winit`] adapter crate.
 //!
 //! ## Features
 //!
 //! - `std` (enabled by default): Use the Rust standard library.
+//! - `dom`: Conversion of pointer events into the shape of DOM events, in the `dom` module.
+//! - `kurbo`: Hit testing of pointer states against [`kurbo`] shapes. This depends on `std`.
+//! - `serde`: Serialization of pointer, scroll, gesture, and keyboard events with [`serde`], for recording and replaying input. This enables `std`, which the `serde` support of `dpi` requires.
 //!
 //! [`ui-events-winit`]: https://docs.rs/ui-events-winit/
+//! [`kurbo`]: https://docs.rs/kurbo/
+//! [`serde`]: https://docs.rs/serde/
 //! [`winit`]: https://docs.rs/winit/
 // LINEBENDER LINT SET - lib.rs - v3
 // See https://linebender.org/wiki/canonical-lints/
@@ -32,9 +39,14 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![no_std]
 
+#[cfg(feature = "dom")]
+pub mod dom;
+pub mod gesture;
 pub mod keyboard;
 pub mod pointer;
 
 mod scroll;
+mod tagged;
 
-pub use scroll::ScrollDelta;
+pub use scroll::{ScrollDelta, ScrollSteps, ScrollUnit};
+pub use tagged::Tagged;
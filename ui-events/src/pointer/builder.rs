@@ -0,0 +1,253 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::alloc::vec::Vec;
+
+use dpi::PhysicalPosition;
+use keyboard_types::Modifiers;
+
+use super::{
+    PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerId, PointerInfo,
+    PointerScrollEvent, PointerState, PointerType, PointerUpdate,
+};
+use crate::ScrollDelta;
+
+/// A builder of synthetic [`PointerEvent`]s, for testing code which handles them.
+///
+/// By default, events are from the primary mouse at the origin, with no buttons
+/// or modifiers held. Set what matters to the test, then finish with
+/// [`down`](Self::down), [`up`](Self::up), [`moved`](Self::moved), or
+/// [`scroll`](Self::scroll). The builder is [`Clone`], so a partially configured
+/// builder can be reused for several events.
+///
+/// ```
+/// use ui_events::pointer::{PointerButton, PointerEvent, PointerEventBuilder};
+///
+/// let press = PointerEventBuilder::new()
+///     .position(10.0, 20.0)
+///     .button(PointerButton::Secondary)
+///     .down();
+/// assert!(matches!(press, PointerEvent::Down(..)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PointerEventBuilder {
+    pointer: PointerInfo,
+    state: PointerState,
+    button: Option<PointerButton>,
+    count: Option<u8>,
+    pressure: Option<f32>,
+    previous_position: Option<PhysicalPosition<f64>>,
+}
+
+impl Default for PointerEventBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PointerEventBuilder {
+    /// A builder for events of the primary mouse.
+    pub fn new() -> Self {
+        Self {
+            pointer: PointerInfo {
+                pointer_id: Some(PointerId::PRIMARY),
+                persistent_device_id: None,
+                pointer_type: PointerType::Mouse,
+            },
+            state: PointerState::default(),
+            button: Some(PointerButton::Primary),
+            count: None,
+            pressure: None,
+            previous_position: None,
+        }
+    }
+
+    /// Set the [`PointerId`].
+    pub fn pointer_id(mut self, pointer_id: PointerId) -> Self {
+        self.pointer.pointer_id = Some(pointer_id);
+        self
+    }
+
+    /// Set the [`PointerType`].
+    ///
+    /// Presses of touches and pens have no button by default, like contacts.
+    pub fn pointer_type(mut self, pointer_type: PointerType) -> Self {
+        if pointer_type != PointerType::Mouse {
+            self.button = None;
+        }
+        self.pointer.pointer_type = pointer_type;
+        self
+    }
+
+    /// Set the position.
+    pub fn position(mut self, x: f64, y: f64) -> Self {
+        self.state.position = PhysicalPosition::new(x, y);
+        self
+    }
+
+    /// Set the position of the previous update, for [`moved`](Self::moved).
+    pub fn previous_position(mut self, x: f64, y: f64) -> Self {
+        self.previous_position = Some(PhysicalPosition::new(x, y));
+        self
+    }
+
+    /// Set the button pressed or released, for [`down`](Self::down) and [`up`](Self::up).
+    ///
+    /// The default is [`PointerButton::Primary`] for the mouse, and no button otherwise.
+    pub fn button(mut self, button: impl Into<Option<PointerButton>>) -> Self {
+        self.button = button.into();
+        self
+    }
+
+    /// Set the buttons held before the event.
+    pub fn buttons(mut self, buttons: PointerButtons) -> Self {
+        self.state.buttons = buttons;
+        self
+    }
+
+    /// Set the modifiers.
+    pub fn modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.state.modifiers = modifiers;
+        self
+    }
+
+    /// Set the click or tap count.
+    ///
+    /// The default is 1 for [`down`](Self::down) and [`up`](Self::up), and 0 otherwise.
+    pub fn count(mut self, count: u8) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Set the pressure.
+    ///
+    /// The default is 0.5 while a button is held, and 0.0 otherwise.
+    pub fn pressure(mut self, pressure: f32) -> Self {
+        self.pressure = Some(pressure);
+        self
+    }
+
+    /// Set the time, in nanoseconds.
+    pub fn time(mut self, time: u64) -> Self {
+        self.state.time = time;
+        self
+    }
+
+    /// Build a [`PointerEvent::Down`], with the [`button`](Self::button) held.
+    pub fn down(mut self) -> PointerEvent {
+        self.state
+            .buttons
+            .insert(self.button.unwrap_or(PointerButton::Primary));
+        PointerEvent::Down(PointerButtonEvent {
+            button: self.button,
+            pointer: self.pointer,
+            state: self.finish(1),
+        })
+    }
+
+    /// Build a [`PointerEvent::Up`], with the [`button`](Self::button) released.
+    pub fn up(mut self) -> PointerEvent {
+        self.state
+            .buttons
+            .remove(self.button.unwrap_or(PointerButton::Primary));
+        PointerEvent::Up(PointerButtonEvent {
+            button: self.button,
+            pointer: self.pointer,
+            state: self.finish(1),
+        })
+    }
+
+    /// Build a [`PointerEvent::Move`].
+    pub fn moved(self) -> PointerEvent {
+        let previous_position = self.previous_position;
+        let pointer = self.pointer;
+        PointerEvent::Move(PointerUpdate {
+            pointer,
+            current: self.finish(0),
+            previous_position,
+            down_position: None,
+            velocity: None,
+            coalesced: Vec::new(),
+            predicted: Vec::new(),
+        })
+    }
+
+    /// Build a [`PointerEvent::Scroll`] by `delta`.
+    pub fn scroll(self, delta: ScrollDelta) -> PointerEvent {
+        let pointer = self.pointer;
+        PointerEvent::Scroll(PointerScrollEvent {
+            pointer,
+            delta,
+            raw_delta: delta,
+            notches: None,
+            state: self.finish(0),
+        })
+    }
+
+    /// The state of the event, with `default_count` unless a count was set.
+    fn finish(self, default_count: u8) -> PointerState {
+        let held = if self.pointer.pointer_type == PointerType::Mouse {
+            !self.state.buttons.is_empty()
+        } else {
+            self.state.buttons.contains(PointerButton::Primary)
+        };
+        PointerState {
+            count: self.count.unwrap_or(default_count),
+            pressure: self.pressure.unwrap_or(if held { 0.5 } else { 0.0 }),
+            ..self.state
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Events have ergonomic defaults, and use what was set.
+    #[test]
+    fn builder() {
+        let PointerEvent::Down(down) = PointerEventBuilder::new().position(1.0, 2.0).down() else {
+            panic!("expected a down");
+        };
+        assert!(down.pointer.is_primary_pointer(), "the primary mouse");
+        assert_eq!(
+            down.button,
+            Some(PointerButton::Primary),
+            "the primary button"
+        );
+        assert!(
+            down.state.buttons.contains(PointerButton::Primary),
+            "the button is held"
+        );
+        assert_eq!(
+            (down.state.count, down.state.pressure),
+            (1, 0.5),
+            "a single click with pressure"
+        );
+
+        let touch = PointerEventBuilder::new()
+            .pointer_type(PointerType::Touch)
+            .pointer_id(PointerId::new(7).unwrap());
+        let PointerEvent::Up(up) = touch.clone().count(2).up() else {
+            panic!("expected an up");
+        };
+        assert_eq!(up.button, None, "a touch contact has no button");
+        assert_eq!(
+            (up.state.count, up.state.pressure),
+            (2, 0.0),
+            "a double tap lifted"
+        );
+
+        let PointerEvent::Move(update) =
+            touch.position(3.0, 3.0).previous_position(1.0, 1.0).moved()
+        else {
+            panic!("expected a move");
+        };
+        assert_eq!(
+            update.delta(),
+            PhysicalPosition::new(2.0, 2.0),
+            "the move has a delta"
+        );
+        assert_eq!(update.current.count, 0, "moves have no count");
+    }
+}
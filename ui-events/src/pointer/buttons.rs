@@ -9,6 +9,7 @@
 /// in practice, and Windows doesn't support more than 32 mouse buttons
 /// in most APIs, therefore 32 was chosen as the upper limit.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum PointerButton {
     /// Primary button, commonly the left mouse button, touch contact, pen contact.
@@ -79,6 +80,7 @@ pub enum PointerButton {
 
 /// A set of [`PointerButton`]s.
 #[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerButtons(u32);
 
 impl PointerButtons {
@@ -3,8 +3,10 @@
 
 //! Pointer Event Types
 
+mod builder;
 mod buttons;
 
+pub use builder::PointerEventBuilder;
 pub use buttons::{PointerButton, PointerButtons};
 
 extern crate alloc;
@@ -15,12 +17,13 @@ use core::num::NonZeroU64;
 use dpi::{PhysicalPosition, PhysicalSize};
 use keyboard_types::Modifiers;
 
-use crate::ScrollDelta;
+use crate::{ScrollDelta, ScrollUnit};
 
 /// A unique identifier for the pointer.
 ///
 /// PointerId(1) is reserved for the primary pointer.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerId(NonZeroU64);
 
 impl PointerId {
@@ -38,12 +41,40 @@ impl PointerId {
     pub fn is_primary_pointer(self) -> bool {
         self == Self::PRIMARY
     }
+
+    /// Return this `PointerId` with its generation replaced by `generation`.
+    ///
+    /// Event sources which recycle platform pointer IDs can give each new pointer
+    /// a new generation, so that a stale ID of a lifted pointer doesn't compare equal
+    /// to the ID of the pointer which reuses it. The generation is stored in the
+    /// highest 16 bits, so the rest of the ID must fit in 48 bits.
+    ///
+    /// Returns `None` if the resulting ID would be zero.
+    #[inline(always)]
+    pub fn with_generation(self, generation: u16) -> Option<Self> {
+        Self::new(self.0.get() & Self::ID_MASK | u64::from(generation) << Self::GENERATION_SHIFT)
+    }
+
+    /// The generation of this `PointerId`, which is `0` unless the event source recycles IDs.
+    ///
+    /// See [`PointerId::with_generation`].
+    #[inline(always)]
+    pub fn generation(self) -> u16 {
+        (self.0.get() >> Self::GENERATION_SHIFT) as u16
+    }
+
+    /// Position of the generation in the ID.
+    const GENERATION_SHIFT: u32 = 48;
+
+    /// Bits of the ID which are not the generation.
+    const ID_MASK: u64 = (1 << Self::GENERATION_SHIFT) - 1;
 }
 
 /// An identifier for the pointing device that is stable across the session.
 ///
 /// PointerId(1) is reserved for the primary pointer.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PersistentDeviceId(NonZeroU64);
 
 impl PersistentDeviceId {
@@ -57,6 +88,7 @@ impl PersistentDeviceId {
 /// The type of device that has generated a pointer event.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum PointerType {
     /// The type of device could not be determined.
@@ -72,6 +104,7 @@ pub enum PointerType {
 
 /// Identifying information about a pointer, stable across states.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerInfo {
     /// Pointer ID.
     ///
@@ -101,6 +134,7 @@ impl PointerInfo {
 
 /// Orientation of a pointer.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerOrientation {
     /// Spherical altitude.
     ///
@@ -129,6 +163,7 @@ pub type ContactGeometry = PhysicalSize<f64>;
 
 /// A single pointer state.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerState {
     /// `u64` nanoseconds real time.
     ///
@@ -165,6 +200,19 @@ pub struct PointerState {
     pub tangential_pressure: f32,
 }
 
+impl PointerState {
+    /// Returns `true` if the position is within `rect`.
+    ///
+    /// Like the position, `rect` is in physical pixels. It includes its top and left
+    /// edges but not its bottom and right edges, so adjacent rectangles never both
+    /// contain a position.
+    #[cfg(feature = "kurbo")]
+    #[inline(always)]
+    pub fn is_within(&self, rect: kurbo::Rect) -> bool {
+        rect.contains(kurbo::Point::new(self.position.x, self.position.y))
+    }
+}
+
 impl Default for PointerState {
     fn default() -> Self {
         Self {
@@ -187,14 +235,36 @@ impl Default for PointerState {
 
 /// A pointer update, along with coalesced and predicted states.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerUpdate {
     /// Identifying information about pointer.
     pub pointer: PointerInfo,
     /// Current state.
+    ///
+    /// This is the authoritative, newest state of the pointer,
+    /// which is newer than every state in [`coalesced`](Self::coalesced).
     pub current: PointerState,
-    /// Coalesced states, ordered by `time`.
+    /// Position of the previous update for this pointer.
+    ///
+    /// This is `None` where the previous position is not known,
+    /// such as for the first update after the pointer entered.
+    pub previous_position: Option<PhysicalPosition<f64>>,
+    /// Position where the pointer went down, for drags.
     ///
-    /// Coalescing is application-specific.
+    /// This is `None` when no button is held, or for a touch or pen without contact.
+    /// When several buttons are held, this is where the first of them was pressed.
+    pub down_position: Option<PhysicalPosition<f64>>,
+    /// Velocity of the pointer, in physical pixels per second.
+    ///
+    /// This is `None` where the event source doesn't report it. It is zero for
+    /// the first update after a touch starts, as there is no earlier move to
+    /// measure against.
+    pub velocity: Option<PhysicalPosition<f64>>,
+    /// Coalesced states, ordered by `time` from oldest to newest.
+    ///
+    /// These are the intermediate states since the previous update, which are
+    /// all older than [`current`](Self::current), for consumers which need every
+    /// sample, like drawing applications. Coalescing is application-specific.
     /// On the web, the browser does its own coalescing, whereas
     /// on other platforms you may do your own, or forego it
     /// altogether, delivering every state.
@@ -212,12 +282,43 @@ impl PointerUpdate {
     pub fn is_primary_pointer(&self) -> bool {
         self.pointer.is_primary_pointer()
     }
+
+    /// Returns the newest state, which is [`current`](Self::current).
+    ///
+    /// Use this when only the latest position matters, and
+    /// [`coalesced`](Self::coalesced) for the states before it.
+    #[inline(always)]
+    pub fn latest(&self) -> &PointerState {
+        &self.current
+    }
+
+    /// Returns the change in position since the previous update for this pointer.
+    ///
+    /// Like positions, this is in physical pixels, so it is consistent even when the
+    /// scale factor changes between the two updates. Divide it by the current scale
+    /// factor to get a logical delta.
+    ///
+    /// This is zero when [`previous_position`](Self::previous_position) is `None`.
+    pub fn delta(&self) -> PhysicalPosition<f64> {
+        match self.previous_position {
+            Some(previous) => PhysicalPosition::new(
+                self.current.position.x - previous.x,
+                self.current.position.y - previous.y,
+            ),
+            None => PhysicalPosition::default(),
+        }
+    }
 }
 
 /// An event representing a [`PointerButton`] that was pressed or released.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerButtonEvent {
     /// The [`PointerButton`] that was pressed.
+    ///
+    /// This is `None` for the contact of a touch or pen without a button,
+    /// which is treated like [`PointerButton::Primary`]. It is also `None` for a
+    /// button which has no [`PointerButton`] representation.
     pub button: Option<PointerButton>,
     /// Identity of the pointer.
     pub pointer: PointerInfo,
@@ -225,17 +326,85 @@ pub struct PointerButtonEvent {
     pub state: PointerState,
 }
 
+impl PointerButtonEvent {
+    /// Returns the [`PointerButton`], treating a contact without a button as
+    /// [`PointerButton::Primary`].
+    #[inline(always)]
+    pub fn button_or_primary(&self) -> PointerButton {
+        self.button.unwrap_or(PointerButton::Primary)
+    }
+
+    /// Returns `true` if this press starts a new click or tap sequence.
+    ///
+    /// This is the case when [`count`](PointerState::count) is 1.
+    #[inline(always)]
+    pub fn starts_sequence(&self) -> bool {
+        self.state.count == 1
+    }
+
+    /// Returns `true` if all of `modifiers` were held when this button was pressed or released.
+    ///
+    /// The modifiers of each press and release are those held at its own time,
+    /// so the presses of a chord can be checked separately.
+    #[inline(always)]
+    pub fn has_modifier(&self, modifiers: Modifiers) -> bool {
+        self.state.modifiers.contains(modifiers)
+    }
+
+    /// Returns `true` if this press continues a click or tap sequence,
+    /// for example the second press of a double click.
+    ///
+    /// This is the case when [`count`](PointerState::count) is greater than 1.
+    ///
+    /// A sequence stays open for a short interval after each release, so a press
+    /// that starts or continues a sequence can itself be continued. Once that interval
+    /// has passed, the next press starts a new sequence. The interval and the maximum
+    /// distance between presses are chosen by the event source.
+    #[inline(always)]
+    pub fn continues_sequence(&self) -> bool {
+        self.state.count > 1
+    }
+}
+
 /// An event representing a scroll
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerScrollEvent {
     /// Identity of the pointer.
     pub pointer: PointerInfo,
     /// The delta of the scroll.
+    ///
+    /// Event sources may scale this, for example by a scroll sensitivity.
     pub delta: ScrollDelta,
+    /// The delta of the scroll as reported by the platform, before any scaling.
+    ///
+    /// This is for consumers which implement their own scaling, such as
+    /// physics based scrolling. Like [`delta`](Self::delta), a
+    /// [`LineDelta`](ScrollDelta::LineDelta) is in platform lines, whose size
+    /// in pixels is up to the consumer, and a [`PixelDelta`](ScrollDelta::PixelDelta)
+    /// is in physical pixels, which can be divided by the scale factor
+    /// to get logical pixels. Event sources don't apply the scale factor to
+    /// pixel deltas, so a change of scale factor during a scroll doesn't
+    /// change the amount scrolled in physical pixels.
+    pub raw_delta: ScrollDelta,
+    /// Whole wheel notches (detents) in this scroll, horizontal then vertical.
+    ///
+    /// This is `None` when the scroll did not come from a notched wheel.
+    /// Event sources accumulate fractional notches from high resolution wheels,
+    /// so this counts each notch exactly once, and may be zero for some events.
+    pub notches: Option<(i32, i32)>,
     /// The state of the pointer (i.e. position, pressure, etc.).
     pub state: PointerState,
 }
 
+impl PointerScrollEvent {
+    /// The unit of the scroll, see [`ScrollDelta::unit`].
+    #[inline(always)]
+    pub fn unit(&self) -> ScrollUnit {
+        self.delta.unit()
+    }
+}
+
 /// A standard `PointerEvent`.
 ///
 /// This is intentionally limited to standard pointer events,
@@ -243,6 +412,7 @@ pub struct PointerScrollEvent {
 /// support more event types will use this as a base and add
 /// what they need in a conversion.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PointerEvent {
     /// A [`PointerButton`] was pressed.
     Down(PointerButtonEvent),
@@ -256,9 +426,14 @@ pub enum PointerEvent {
     /// You should try to undo the effect of the gesture when you receive this.
     Cancel(PointerInfo),
     /// Pointer entered the area that receives this event.
-    Enter(PointerInfo),
+    ///
+    /// The [`PointerState`] is that of the pointer where it entered.
+    Enter(PointerInfo, PointerState),
     /// Pointer left the area that receives these events.
-    Leave(PointerInfo),
+    ///
+    /// The [`PointerState`] is the last state of the pointer before it left,
+    /// including any buttons which are still held.
+    Leave(PointerInfo, PointerState),
     /// A scroll was requested at the pointer location.
     ///
     /// Usually this is caused by a mouse wheel or a touchpad.
@@ -274,9 +449,98 @@ impl PointerEvent {
             | Self::Up(PointerButtonEvent { pointer, .. })
             | Self::Move(PointerUpdate { pointer, .. })
             | Self::Cancel(pointer)
-            | Self::Enter(pointer)
-            | Self::Leave(pointer)
+            | Self::Enter(pointer, _)
+            | Self::Leave(pointer, _)
             | Self::Scroll(PointerScrollEvent { pointer, .. }) => pointer.is_primary_pointer(),
         }
     }
+
+    /// Returns `true` if this is a [`PointerEvent::Up`] after which no buttons are held.
+    ///
+    /// This means that the release emptied the set of held buttons, which is useful
+    /// to detect the end of a drag involving several buttons. The contact of a touch
+    /// holds no buttons, so only its up goes idle, not its down.
+    #[inline(always)]
+    pub fn went_idle(&self) -> bool {
+        matches!(self, Self::Up(event) if event.state.buttons.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions are hit tested against rectangles.
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn is_within() {
+        let state = |x, y| PointerState {
+            position: PhysicalPosition::new(x, y),
+            ..Default::default()
+        };
+        let rect = kurbo::Rect::new(10.0, 10.0, 20.0, 20.0);
+        assert!(state(15.0, 10.0).is_within(rect), "inside, on the top edge");
+        assert!(!state(20.0, 15.0).is_within(rect), "on the right edge");
+        assert!(!state(5.0, 15.0).is_within(rect), "outside");
+    }
+
+    /// Pointer events survive a round trip through JSON.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut current = PointerState {
+            time: 42,
+            position: PhysicalPosition::new(1.5, -2.0),
+            modifiers: Modifiers::SHIFT,
+            count: 2,
+            pressure: 0.5,
+            ..Default::default()
+        };
+        current.buttons.insert(PointerButton::Primary);
+        let update = PointerUpdate {
+            pointer: PointerInfo {
+                pointer_id: PointerId::new(3),
+                persistent_device_id: PersistentDeviceId::new(9),
+                pointer_type: PointerType::Pen,
+            },
+            coalesced: alloc::vec![PointerState {
+                time: 40,
+                ..current.clone()
+            }],
+            predicted: Vec::new(),
+            previous_position: Some(PhysicalPosition::new(1.0, -2.0)),
+            down_position: None,
+            velocity: Some(PhysicalPosition::new(10.0, 0.0)),
+            current,
+        };
+        let json = serde_json::to_string(&PointerEvent::Move(update.clone())).unwrap();
+        let PointerEvent::Move(decoded) = serde_json::from_str(&json).unwrap() else {
+            panic!("expected a move, got {json}");
+        };
+        assert_eq!(decoded, update, "the move is unchanged");
+    }
+
+    /// The latest state is the current one, not a coalesced state.
+    #[test]
+    fn latest() {
+        let state = |time, x| PointerState {
+            time,
+            position: PhysicalPosition::new(x, 0.0),
+            ..Default::default()
+        };
+        let update = PointerUpdate {
+            pointer: PointerInfo {
+                pointer_id: Some(PointerId::PRIMARY),
+                persistent_device_id: None,
+                pointer_type: PointerType::Mouse,
+            },
+            current: state(3, 3.0),
+            previous_position: None,
+            down_position: None,
+            velocity: None,
+            coalesced: alloc::vec![state(1, 1.0), state(2, 2.0)],
+            predicted: Vec::new(),
+        };
+        assert_eq!(update.latest(), &update.current, "the latest is current");
+    }
 }
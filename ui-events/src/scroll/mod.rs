@@ -15,6 +15,7 @@ use dpi::PhysicalPosition;
 /// For scroll deltas generated by scrollbars or other elements, `PageDelta`
 /// may be used (for example, when clicking in the well of the scrollbar).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScrollDelta {
     PageDelta(f32, f32),
 
@@ -22,3 +23,250 @@ pub enum ScrollDelta {
 
     PixelDelta(PhysicalPosition<f64>),
 }
+
+/// The unit that a scroll is meant in, see [`ScrollDelta::unit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollUnit {
+    /// Scroll by pixels, as touchpads do.
+    Pixel,
+    /// Scroll by lines, as mouse wheels usually do.
+    Line,
+    /// Scroll by pages, as scrollbar wells and some mouse wheel settings do.
+    Page,
+}
+
+impl ScrollDelta {
+    /// The unit of this delta, which is that of its variant.
+    ///
+    /// Use [`ScrollDelta::unit_with_page_height`] to also treat scrolls of a page
+    /// or more as page scrolls.
+    pub fn unit(&self) -> ScrollUnit {
+        match self {
+            Self::PageDelta(..) => ScrollUnit::Page,
+            Self::LineDelta(..) => ScrollUnit::Line,
+            Self::PixelDelta(..) => ScrollUnit::Pixel,
+        }
+    }
+
+    /// The unit of this delta, where scrolls of at least `page_height` are page scrolls.
+    ///
+    /// Some platforms can be configured to scroll a page for each wheel notch,
+    /// and report it as a very large line delta. Here, line and pixel deltas
+    /// which scroll at least `page_height` pixels along either axis are
+    /// [`ScrollUnit::Page`], with lines of `line_height` pixels.
+    pub fn unit_with_page_height(&self, page_height: f64, line_height: f64) -> ScrollUnit {
+        let (x, y) = match *self {
+            Self::LineDelta(x, y) => (f64::from(x) * line_height, f64::from(y) * line_height),
+            Self::PixelDelta(p) => (p.x, p.y),
+            Self::PageDelta(..) => return ScrollUnit::Page,
+        };
+        if x.abs().max(y.abs()) >= page_height {
+            ScrollUnit::Page
+        } else {
+            self.unit()
+        }
+    }
+
+    /// Returns `true` if this delta doesn't scroll, which some platforms emit.
+    pub fn is_zero(&self) -> bool {
+        self.components() == (0.0, 0.0)
+    }
+
+    /// Returns `true` if this delta mostly scrolls horizontally.
+    ///
+    /// The dominant axis is the one with the larger magnitude. When both axes
+    /// have the same nonzero magnitude, the delta is vertical, as that is the
+    /// more common direction of scrolling. A zero delta is neither.
+    pub fn is_horizontal(&self) -> bool {
+        let (x, y) = self.components();
+        x.abs() > y.abs()
+    }
+
+    /// Returns `true` if this delta mostly scrolls vertically.
+    ///
+    /// See [`ScrollDelta::is_horizontal`] for the dominant axis rule.
+    pub fn is_vertical(&self) -> bool {
+        let (x, y) = self.components();
+        y != 0.0 && y.abs() >= x.abs()
+    }
+
+    /// The horizontal and vertical components, in the unit of the variant.
+    fn components(&self) -> (f64, f64) {
+        match *self {
+            Self::PageDelta(x, y) | Self::LineDelta(x, y) => (f64::from(x), f64::from(y)),
+            Self::PixelDelta(p) => (p.x, p.y),
+        }
+    }
+
+    /// Convert this delta into whole steps, such as rows and columns of a list.
+    ///
+    /// `step_pixels` is the size of a step in pixels, and `line_height` is the size of
+    /// a line of a [`LineDelta`](Self::LineDelta) in pixels. Steps are horizontal then
+    /// vertical, and truncated toward zero, so use [`ScrollSteps`] to keep the
+    /// fractional remainder across events.
+    ///
+    /// A [`PageDelta`](Self::PageDelta) depends on the size of the viewport,
+    /// so it is always zero steps.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_steps(&self, step_pixels: f64, line_height: f64) -> (i32, i32) {
+        let (x, y) = self.fractional_steps(step_pixels, line_height);
+        (x as i32, y as i32)
+    }
+
+    /// Fractional steps in this delta, see [`ScrollDelta::to_steps`].
+    fn fractional_steps(&self, step_pixels: f64, line_height: f64) -> (f64, f64) {
+        match *self {
+            Self::PageDelta(..) => (0.0, 0.0),
+            Self::LineDelta(x, y) => (
+                f64::from(x) * line_height / step_pixels,
+                f64::from(y) * line_height / step_pixels,
+            ),
+            Self::PixelDelta(p) => (p.x / step_pixels, p.y / step_pixels),
+        }
+    }
+}
+
+/// Accumulates [`ScrollDelta`]s into whole steps, such as rows and columns of a list.
+///
+/// Small deltas, like those of touchpads and high resolution wheels, add up
+/// to a step over several events, so scrolling stays smooth. When the direction
+/// of an axis reverses, the remainder of that axis is discarded, so a reversal
+/// doesn't fire a step in the old direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollSteps {
+    /// Fractional steps not yet reported, horizontal then vertical.
+    remainder: (f64, f64),
+}
+
+impl ScrollSteps {
+    /// Add `delta`, and return the whole steps which are now complete.
+    ///
+    /// See [`ScrollDelta::to_steps`] for the meaning of the arguments.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn accumulate(
+        &mut self,
+        delta: &ScrollDelta,
+        step_pixels: f64,
+        line_height: f64,
+    ) -> (i32, i32) {
+        let (x, y) = delta.fractional_steps(step_pixels, line_height);
+        let (rx, ry) = &mut self.remainder;
+        *rx = accumulate_axis(*rx, x);
+        *ry = accumulate_axis(*ry, y);
+        let steps = (*rx as i32, *ry as i32);
+        *rx -= f64::from(steps.0);
+        *ry -= f64::from(steps.1);
+        steps
+    }
+
+    /// Forget the fractional remainder, for example when the list changes.
+    pub fn reset(&mut self) {
+        self.remainder = (0.0, 0.0);
+    }
+}
+
+/// Add `steps` to `remainder`, discarding the remainder if the direction reversed.
+fn accumulate_axis(remainder: f64, steps: f64) -> f64 {
+    if remainder * steps < 0.0 {
+        steps
+    } else {
+        remainder + steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lines and pixels are converted into steps.
+    #[test]
+    fn to_steps() {
+        let lines = ScrollDelta::LineDelta(1.0, -3.0);
+        assert_eq!(lines.to_steps(20.0, 20.0), (1, -3), "a line is a step");
+        assert_eq!(lines.to_steps(40.0, 20.0), (0, -1), "truncated toward zero");
+        let pixels = ScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 50.0));
+        assert_eq!(pixels.to_steps(20.0, 16.0), (0, 2), "pixels");
+        let pages = ScrollDelta::PageDelta(1.0, 1.0);
+        assert_eq!(pages.to_steps(20.0, 16.0), (0, 0), "pages are not steps");
+    }
+
+    /// Zero deltas are detected, and the dominant axis is the larger one.
+    #[test]
+    fn axes() {
+        let zero = ScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 0.0));
+        assert!(zero.is_zero(), "a zero delta");
+        assert!(
+            !zero.is_horizontal() && !zero.is_vertical(),
+            "a zero delta has no axis"
+        );
+        let horizontal = ScrollDelta::LineDelta(-2.0, 1.0);
+        assert!(
+            !horizontal.is_zero() && horizontal.is_horizontal() && !horizontal.is_vertical(),
+            "a mostly horizontal delta"
+        );
+        let vertical = ScrollDelta::PageDelta(0.0, -1.0);
+        assert!(
+            vertical.is_vertical() && !vertical.is_horizontal(),
+            "a vertical delta"
+        );
+        let diagonal = ScrollDelta::LineDelta(1.0, 1.0);
+        assert!(diagonal.is_vertical(), "a diagonal delta is vertical");
+    }
+
+    /// Deltas are classified by their variant, and by their size with a page height.
+    #[test]
+    fn units() {
+        let line = ScrollDelta::LineDelta(0.0, -3.0);
+        assert_eq!(line.unit(), ScrollUnit::Line, "a line delta");
+        assert_eq!(
+            line.unit_with_page_height(100.0, 20.0),
+            ScrollUnit::Line,
+            "less than a page"
+        );
+        assert_eq!(
+            line.unit_with_page_height(60.0, 20.0),
+            ScrollUnit::Page,
+            "a page of lines"
+        );
+        let pixels = ScrollDelta::PixelDelta(PhysicalPosition::new(5.0, 0.0));
+        assert_eq!(
+            pixels.unit_with_page_height(100.0, 20.0),
+            ScrollUnit::Pixel,
+            "a pixel delta"
+        );
+    }
+
+    /// Fractions of steps add up across events.
+    #[test]
+    fn accumulated_steps() {
+        let mut steps = ScrollSteps::default();
+        let delta = ScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 8.0));
+        assert_eq!(steps.accumulate(&delta, 20.0, 16.0), (0, 0), "8 px");
+        assert_eq!(steps.accumulate(&delta, 20.0, 16.0), (0, 0), "16 px");
+        assert_eq!(steps.accumulate(&delta, 20.0, 16.0), (0, 1), "24 px");
+        steps.reset();
+        assert_eq!(steps.accumulate(&delta, 20.0, 16.0), (0, 0), "reset");
+    }
+
+    /// A reversal of direction discards the remainder of the old direction.
+    #[test]
+    fn reversed_steps() {
+        let mut steps = ScrollSteps::default();
+        let up = ScrollDelta::LineDelta(0.0, -0.75);
+        let down = ScrollDelta::LineDelta(0.0, 0.5);
+        assert_eq!(steps.accumulate(&up, 1.0, 1.0), (0, 0), "most of a step up");
+        assert_eq!(
+            steps.accumulate(&down, 1.0, 1.0),
+            (0, 0),
+            "half a step down"
+        );
+        assert_eq!(
+            steps.accumulate(&up, 1.0, 1.0),
+            (0, 0),
+            "no spurious step up"
+        );
+        assert_eq!(steps.accumulate(&down, 1.0, 1.0), (0, 0), "reversed again");
+        assert_eq!(steps.accumulate(&down, 1.0, 1.0), (0, 1), "a step down");
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// An event tagged with context from the pipeline it flows through.
+///
+/// Routing layers can use this to attach data such as the widget under the
+/// pointer or a frame number to an event, without a parallel side channel.
+/// The context defaults to a `u64`, which is enough for most identifiers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tagged<E, C = u64> {
+    /// The event.
+    pub event: E,
+    /// Context attached to the event.
+    pub context: C,
+}
+
+impl<E, C> Tagged<E, C> {
+    /// Tag `event` with `context`.
+    #[inline(always)]
+    pub fn new(event: E, context: C) -> Self {
+        Self { event, context }
+    }
+
+    /// Replace the context, keeping the event.
+    #[inline]
+    pub fn map_context<D>(self, f: impl FnOnce(C) -> D) -> Tagged<E, D> {
+        Tagged {
+            event: self.event,
+            context: f(self.context),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::{PointerEvent, PointerId, PointerInfo, PointerState, PointerType};
+
+    /// Context can be attached to an event and replaced.
+    #[test]
+    fn tagged() {
+        let pointer = PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: None,
+            pointer_type: PointerType::Mouse,
+        };
+        let tagged = Tagged::new(PointerEvent::Leave(pointer, PointerState::default()), 7);
+        assert_eq!(tagged.context, 7, "the context is attached");
+        let tagged = tagged.map_context(|frame| (frame, "widget"));
+        assert_eq!(tagged.context, (7, "widget"), "the context is replaced");
+        assert!(
+            matches!(tagged.event, PointerEvent::Leave(p, _) if p == pointer),
+            "the event is kept"
+        );
+    }
+}